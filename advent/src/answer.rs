@@ -0,0 +1,93 @@
+/// Something a part can return. Answers are shown as rendered, and compared after normalising
+/// so whitespace around and at the ends of lines doesn't matter.
+pub trait Answer {
+    /// The answer as printed, which may take several lines.
+    fn render(&self) -> String;
+
+    /// The answer on one line, for JSON and tables, with the lines of multi-line answers joined
+    /// by `/`.
+    fn one_line(&self) -> String {
+        one_line(&self.render())
+    }
+
+    /// Whether this is the answer rendered as `expected`.
+    fn matches(&self, expected: &str) -> bool {
+        same(&self.render(), expected)
+    }
+}
+
+/// An answer's lines without trailing whitespace, and without blank lines at either end.
+pub fn normalise(answer: &str) -> String {
+    let lines: Vec<_> = answer.lines().map(str::trim_end).collect();
+    let start = lines
+        .iter()
+        .position(|l| !l.is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|l| !l.is_empty())
+        .map_or(start, |e| e + 1);
+    lines[start..end].join("\n")
+}
+
+/// Whether two rendered answers are the same once normalised.
+pub fn same(a: &str, b: &str) -> bool {
+    normalise(a) == normalise(b)
+}
+
+pub fn one_line(answer: &str) -> String {
+    normalise(answer).lines().collect::<Vec<_>>().join("/")
+}
+
+/// Panics unless `actual` is the same answer as `expected`, showing both.
+#[track_caller]
+pub fn assert_answer(actual: &impl Answer, expected: &impl Answer) {
+    let (actual, expected) = (actual.render(), expected.render());
+    assert!(
+        same(&actual, &expected),
+        "answers differ\n  actual:\n{actual}\n  expected:\n{expected}"
+    );
+}
+
+macro_rules! answer_by_display {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn render(&self) -> String {
+                self.to_string()
+            }
+        })*
+    }
+}
+
+answer_by_display!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+answer_by_display!(bool, char, str, String);
+
+impl<T: Answer + ?Sized> Answer for &T {
+    fn render(&self) -> String {
+        (**self).render()
+    }
+}
+
+/// Lists of answers render as one line of comma separated values.
+impl<T: Answer> Answer for Vec<T> {
+    fn render(&self) -> String {
+        self.iter()
+            .map(Answer::one_line)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+macro_rules! answer_for_tuple {
+    ($($t:ident $i:tt),*) => {
+        impl<$($t: Answer),*> Answer for ($($t,)*) {
+            fn render(&self) -> String {
+                [$(self.$i.one_line()),*].join(",")
+            }
+        }
+    }
+}
+
+answer_for_tuple!(A 0, B 1);
+answer_for_tuple!(A 0, B 1, C 2);
+answer_for_tuple!(A 0, B 1, C 2, D 3);
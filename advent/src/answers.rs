@@ -0,0 +1,274 @@
+use crate::answer;
+use std::path::Path;
+use std::{fmt, fs, io};
+
+/// Answers confirmed by a human, kept in a day's `answers.toml` as one table per input file:
+///
+/// ```toml
+/// ["input.txt"]
+/// part_1 = "67622"
+/// part_2 = """
+/// multi-line answers use TOML's multi-line strings
+/// """
+/// ```
+///
+/// Only that much TOML is read or written. Answers are compared as printed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    files: Vec<(String, [Option<String>; 2])>,
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut answers = Self::default();
+        let mut lines = s.lines().enumerate();
+        while let Some((i, line)) = lines.next() {
+            let err = |e: &str| format!("answers.toml:{}: {e}", i + 1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let file = match table.strip_prefix('"') {
+                    Some(quoted) => {
+                        unescape(quoted.strip_suffix('"').ok_or(err("bad table name"))?)
+                    }
+                    None => table.to_owned(),
+                };
+                answers.files.push((file, [None, None]));
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or(err("expected `key = value`"))?;
+            let part = match key.trim() {
+                "part_1" => 0,
+                "part_2" => 1,
+                key => return Err(err(&format!("unknown key `{key}`"))),
+            };
+            let value = value.trim();
+            let value = if let Some(first) = value.strip_prefix(MULTI_LINE_QUOTES) {
+                // Runs to the closing quotes, less a newline right after the opening ones.
+                let mut value = first.to_owned();
+                while closing_quotes(&value).is_none() {
+                    let (_, line) = lines.next().ok_or(err("unterminated string"))?;
+                    value.push('\n');
+                    value.push_str(line);
+                }
+                let value = value.strip_prefix('\n').unwrap_or(&value);
+                unescape(&value[..closing_quotes(value).unwrap()])
+            } else {
+                let quoted = value.strip_prefix('"').and_then(|v| v.strip_suffix('"'));
+                unescape(quoted.ok_or(err("expected a string"))?)
+            };
+            let Some((_, parts)) = answers.files.last_mut() else {
+                return Err(err("answer outside of a file's table"));
+            };
+            parts[part] = Some(value);
+        }
+        Ok(answers)
+    }
+
+    /// Reads the answers recorded in `dir`, if there are any.
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        match fs::read_to_string(dir.join(FILE)) {
+            Ok(s) => Self::parse(&s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, dir: &Path) -> io::Result<()> {
+        fs::write(dir.join(FILE), self.to_string())
+    }
+
+    /// How an answer compares with the one recorded for it.
+    pub fn check(&self, file: &str, part: usize, answer: &str) -> Verdict {
+        match self.get(file, part) {
+            None => Verdict::New,
+            Some(recorded) if answer::same(recorded, answer) => Verdict::Pass,
+            Some(recorded) => Verdict::Fail(recorded.to_owned()),
+        }
+    }
+
+    /// The recorded answer for a part of an input file.
+    pub fn get(&self, file: &str, part: usize) -> Option<&str> {
+        let (_, parts) = self.files.iter().find(|(f, _)| f == file)?;
+        parts[part - 1].as_deref()
+    }
+
+    pub fn set(&mut self, file: &str, part: usize, answer: String) {
+        let index = match self.files.iter().position(|(f, _)| f == file) {
+            Some(index) => index,
+            None => {
+                self.files.push((file.to_owned(), [None, None]));
+                self.files.len() - 1
+            }
+        };
+        self.files[index].1[part - 1] = Some(answer);
+    }
+
+    /// Every recorded answer, as the input file, the part and the answer.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize, &str)> {
+        self.files.iter().flat_map(|(file, parts)| {
+            (1..=2).filter_map(move |part| Some((&file[..], part, parts[part - 1].as_deref()?)))
+        })
+    }
+}
+
+pub const FILE: &str = "answers.toml";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    /// Holds the recorded answer.
+    Fail(String),
+    New,
+}
+
+impl Verdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pass => "PASS",
+            Self::Fail(_) => "FAIL",
+            Self::New => "NEW",
+        }
+    }
+}
+
+/// A line by line comparison of a recorded and an actual multi-line answer.
+pub fn diff(recorded: &str, actual: &str) -> String {
+    let (mut recorded, mut actual) = (recorded.lines(), actual.lines());
+    let mut out = String::new();
+    loop {
+        match (recorded.next(), actual.next()) {
+            (None, None) => return out,
+            (Some(r), Some(a)) if r == a => out += &format!("  {r}\n"),
+            (r, a) => {
+                if let Some(r) = r {
+                    out += &format!("- {r}\n");
+                }
+                if let Some(a) = a {
+                    out += &format!("+ {a}\n");
+                }
+            }
+        }
+    }
+}
+
+const MULTI_LINE_QUOTES: &str = "\"\"\"";
+
+/// Where the quotes closing a multi-line string are in `s`, skipping escaped quotes.
+fn closing_quotes(s: &str) -> Option<usize> {
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            _ if s[i..].starts_with(MULTI_LINE_QUOTES) => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(c) => out.push(c),
+            None => {}
+        }
+    }
+    out
+}
+
+fn escape(s: &str, multi_line: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' if !multi_line => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (file, parts)) in self.files.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "[\"{}\"]", escape(file, false))?;
+            for (part, answer) in parts.iter().enumerate() {
+                let Some(answer) = answer else { continue };
+                if answer.contains('\n') {
+                    let answer = escape(answer, true);
+                    writeln!(
+                        f,
+                        "part_{} = {MULTI_LINE_QUOTES}\n{answer}{MULTI_LINE_QUOTES}",
+                        part + 1
+                    )?;
+                } else {
+                    writeln!(f, "part_{} = \"{}\"", part + 1, escape(answer, false))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(file: &str, answer: &str) {
+        let mut answers = Answers::default();
+        answers.set(file, 1, answer.to_owned());
+        answers.set(file, 2, "42".to_owned());
+        let written = answers.to_string();
+        let read = Answers::parse(&written).unwrap();
+        assert_eq!(read, answers, "written as:\n{written}");
+    }
+
+    #[test]
+    fn single_line_answers_round_trip() {
+        round_trip("input.txt", "67622");
+        round_trip("input.txt", "say \"hi\"");
+        round_trip("input.txt", "back\\slash\tand tab");
+        round_trip("input.txt", "\"");
+        round_trip("odd \"name\".txt", "x");
+    }
+
+    #[test]
+    fn multi_line_answers_round_trip() {
+        round_trip("example.txt", "#..#\n####\n#..#");
+        round_trip("example.txt", "x\ny\"");
+        round_trip("example.txt", "x\ny\"\"");
+        round_trip("example.txt", "\"\"\"\n\"\"\"");
+        round_trip("example.txt", "trailing\\\nbackslash\\");
+        round_trip("example.txt", "\nblank first line\n");
+    }
+
+    #[test]
+    fn reads_multi_line_strings() {
+        let answers =
+            Answers::parse("[\"input.txt\"]\npart_1 = \"\"\"\nab\ncd\"\"\"\npart_2 = \"7\"\n")
+                .unwrap();
+        assert_eq!(answers.get("input.txt", 1), Some("ab\ncd"));
+        assert_eq!(answers.get("input.txt", 2), Some("7"));
+        assert!(Answers::parse("[\"input.txt\"]\npart_1 = \"\"\"\nab\\\"\"\"\n").is_err());
+    }
+}
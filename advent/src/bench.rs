@@ -0,0 +1,107 @@
+use crate::json::Json;
+use crate::mem::{self, Usage};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let runs = sorted.len();
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+        let mean = sorted.iter().map(|d| d.as_secs_f64()).sum::<f64>() / runs as f64;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+        Self {
+            runs,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+
+    pub fn json(&self) -> Json {
+        Json::object([
+            ("min_ns", self.min.as_nanos().into()),
+            ("median_ns", self.median.as_nanos().into()),
+            ("mean_ns", self.mean.as_nanos().into()),
+            ("stddev_ns", self.stddev.as_nanos().into()),
+        ])
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:.2?}, median {:.2?}, mean {:.2?}, stddev {:.2?}",
+            self.min, self.median, self.mean, self.stddev
+        )
+    }
+}
+
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let t = f();
+    (t, start.elapsed())
+}
+
+pub struct Measured<R> {
+    pub value: R,
+    /// The single run's time, or the median when benchmarking.
+    pub time: Duration,
+    pub stats: Option<Stats>,
+    /// What the last run allocated, when counting allocations.
+    pub mem: Option<Usage>,
+}
+
+/// Times `run`, once or for `runs` runs after a few untimed warmup runs. `prepare` makes each
+/// run's argument and isn't timed or counted.
+pub fn measure<U, R>(
+    runs: Option<usize>,
+    mut prepare: impl FnMut() -> parse::Result<U>,
+    mut run: impl FnMut(U) -> R,
+) -> parse::Result<Measured<R>> {
+    let runs = runs.map(|runs| runs.max(1));
+    if let Some(runs) = runs {
+        for _ in 0..runs.div_ceil(10) {
+            run(prepare()?);
+        }
+    }
+
+    let mut times = vec![];
+    let mut value = None;
+    let mut usage = None;
+    for _ in 0..runs.unwrap_or(1) {
+        let arg = prepare()?;
+        let ((r, t), u) = mem::track(|| time(|| run(arg)));
+        times.push(t);
+        value = Some(r);
+        usage = u;
+    }
+
+    let stats = runs.map(|_| Stats::from_samples(&times));
+    Ok(Measured {
+        value: value.unwrap(),
+        time: stats.map_or(times[0], |s| s.median),
+        stats,
+        mem: usage,
+    })
+}
@@ -0,0 +1,421 @@
+use crate::answer;
+use crate::answers::{self, Answers, Verdict};
+use crate::bench::{self, Measured};
+use crate::isolate;
+use crate::json::Json;
+use crate::mem;
+use crate::watch;
+use crate::{ParseInput, PartReport, RunPart};
+use std::any::Any;
+use std::io::Read as _;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+use std::{env, fs, io};
+
+const USAGE: &str = "\
+options:
+  --part 1|2          run only one part
+  --input PATH        read the puzzle input from PATH, or stdin for \"-\" (default: input.txt)
+  --example [N]       read example.txt, or exampleN.txt
+  --param KEY=VALUE   override a part's parameter
+  --bench [RUNS]      time parsing and solving over RUNS runs (default 20)
+  --mem               report each phase's allocations and peak heap and resident memory
+  --json              print one JSON object per part
+  --check             compare answers with those recorded in answers.toml
+  --accept            like --check, then offer to record new or changed answers
+  --isolate           run each part in its own process, reporting crashes
+  --timeout SECS      with --isolate, stop a part after SECS seconds
+  --watch             rebuild and re-run whenever the source, input or examples change
+  --reference         run the slower reference implementations, for parts that have one
+  --help              show this message";
+
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    pub json: bool,
+    pub params: Vec<(String, String)>,
+    /// Number of timed runs of each phase, when benchmarking.
+    pub bench: Option<usize>,
+    /// Only run this part, rather than both.
+    pub part: Option<usize>,
+    /// Where to read the input from, `None` meaning stdin.
+    pub input: Option<PathBuf>,
+    /// Run each part in a child process.
+    pub isolate: bool,
+    pub timeout: Option<Duration>,
+    /// Compare answers with the recorded ones.
+    pub check: bool,
+    /// Ask whether to record answers that aren't recorded yet or differ.
+    pub accept: bool,
+    /// Run parts' reference implementations in their place.
+    pub reference: bool,
+    /// Count allocations, which needs [`crate::mem::Counting`] as the global allocator.
+    pub mem: bool,
+    /// Rebuild and re-run on changes.
+    pub watch: bool,
+}
+
+enum Command {
+    Run(RunOptions),
+    Help,
+}
+
+fn number_arg<T: std::str::FromStr>(
+    args: &mut std::iter::Peekable<impl Iterator<Item = String>>,
+) -> Option<T> {
+    let n = args.peek().and_then(|a| a.parse().ok());
+    if n.is_some() {
+        args.next();
+    }
+    n
+}
+
+impl RunOptions {
+    fn from_args(
+        manifest_dir: &Path,
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<Command, String> {
+        let mut options = Self {
+            input: Some(manifest_dir.join("input.txt")),
+            ..Self::default()
+        };
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            match &arg[..] {
+                "--help" | "-h" => return Ok(Command::Help),
+                "--json" => options.json = true,
+                "--mem" => options.mem = true,
+                "--param" => {
+                    let param = args.next().unwrap_or_default();
+                    let Some((key, value)) = param.split_once('=') else {
+                        return Err(format!("expected --param KEY=VALUE, got {param:?}"));
+                    };
+                    options.params.push((key.to_owned(), value.to_owned()));
+                }
+                "--bench" => options.bench = Some(number_arg(&mut args).unwrap_or(20)),
+                "--part" => match number_arg(&mut args) {
+                    Some(part @ (1 | 2)) => options.part = Some(part),
+                    _ => return Err("--part takes 1 or 2".into()),
+                },
+                "--input" => {
+                    let Some(path) = args.next() else {
+                        return Err("--input takes a path, or \"-\" for stdin".into());
+                    };
+                    options.input = (path != "-").then(|| path.into());
+                }
+                "--check" => options.check = true,
+                "--accept" => {
+                    options.check = true;
+                    options.accept = true;
+                }
+                "--isolate" => options.isolate = true,
+                "--reference" => options.reference = true,
+                "--watch" => options.watch = true,
+                "--timeout" => match number_arg::<f64>(&mut args) {
+                    Some(secs) if secs > 0.0 => {
+                        options.isolate = true;
+                        options.timeout = Some(Duration::from_secs_f64(secs));
+                    }
+                    _ => return Err("--timeout takes a number of seconds".into()),
+                },
+                "--example" => {
+                    let file = match number_arg::<usize>(&mut args) {
+                        None | Some(1) => "example.txt".into(),
+                        Some(n) => format!("example{n}.txt"),
+                    };
+                    options.input = Some(manifest_dir.join(file));
+                }
+                _ => return Err(format!("unknown argument {arg:?}")),
+            }
+        }
+        // Absolute, so inputs in the day's directory are recognised however they're named.
+        if let Some(input) = &mut options.input {
+            *input = std::path::absolute(&*input).map_err(|e| e.to_string())?;
+        }
+        if options.check && options.input.is_none() {
+            return Err("--check and --accept need an input file".into());
+        }
+        if options.accept && options.isolate {
+            return Err("--accept can't be used with --isolate".into());
+        }
+        if options.watch && (options.accept || options.input.is_none()) {
+            return Err("--watch can't be used with --accept or input from stdin".into());
+        }
+        Ok(Command::Run(options))
+    }
+
+    /// The name answers for the input are recorded under, relative to the day's directory.
+    fn input_name(&self, manifest_dir: &Path) -> Option<String> {
+        let input = self.input.as_deref()?;
+        Some(
+            input
+                .strip_prefix(manifest_dir)
+                .unwrap_or(input)
+                .display()
+                .to_string(),
+        )
+    }
+
+    fn read_input(&self) -> io::Result<String> {
+        match &self.input {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display()))),
+            None => {
+                let mut input = String::new();
+                io::stdin().lock().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Everything `harness!` knows about a day.
+pub struct Day {
+    pub name: &'static str,
+    pub manifest_dir: &'static str,
+    pub parse: ParseInput,
+    pub parts: [RunPart; 2],
+    /// Each part's reference implementation, if it has one.
+    pub references: [Option<RunPart>; 2],
+}
+
+impl Day {
+    /// Parses the input for both parts, timing it or benchmarking it as `options` say.
+    pub fn parse_input(
+        &self,
+        input: &str,
+        options: &RunOptions,
+    ) -> parse::Result<Measured<Box<dyn Any>>> {
+        let parsed = bench::measure(options.bench, || Ok(()), |()| (self.parse)(input))?;
+        Ok(Measured {
+            value: parsed.value?,
+            time: parsed.time,
+            stats: parsed.stats,
+            mem: parsed.mem,
+        })
+    }
+
+    /// Whether the report matches the answer recorded in `answers.toml` for this input, if there
+    /// is one. The same answers are used by `--check`.
+    pub fn check(&self, input: Option<&Path>, report: &PartReport) -> Option<bool> {
+        let manifest_dir = Path::new(self.manifest_dir);
+        let file = input?.strip_prefix(manifest_dir).ok()?.to_str()?;
+        let answers = Answers::load(manifest_dir).ok()?;
+        let recorded = answers.get(file, report.part)?;
+        Some(
+            report
+                .answer
+                .as_ref()
+                .is_ok_and(|answer| answer::same(recorded, answer)),
+        )
+    }
+
+    /// The trampoline to run for a part, which is its reference implementation if asked for and
+    /// there is one.
+    pub fn part(&self, part: usize, options: &RunOptions) -> RunPart {
+        match (options.reference, self.references[part - 1]) {
+            (true, Some(reference)) => reference,
+            _ => self.parts[part - 1],
+        }
+    }
+}
+
+fn print_report(
+    day: &Day,
+    parsed: &Measured<Box<dyn Any>>,
+    report: &PartReport,
+    matches: Option<bool>,
+    verdict: Option<&Verdict>,
+    reference: bool,
+    json: bool,
+) {
+    if !json {
+        let part = match reference {
+            true => format!("Part {} (reference)", report.part),
+            false => format!("Part {}", report.part),
+        };
+        match &report.answer {
+            Ok(answer) if answer.contains('\n') => println!("{part}:\n{answer}"),
+            Ok(answer) => println!("{part}: {answer}"),
+            Err(e) => eprintln!("{part} failed: {e}"),
+        }
+        match (verdict, &report.answer) {
+            (Some(Verdict::Fail(recorded)), Ok(answer))
+                if answer.contains('\n') || recorded.contains('\n') =>
+            {
+                print!(
+                    "  FAIL, differs from the recorded answer:\n{}",
+                    answers::diff(recorded, answer)
+                );
+            }
+            (Some(Verdict::Fail(recorded)), _) => println!("  FAIL, recorded {recorded}"),
+            (Some(verdict), _) => println!("  {}", verdict.name()),
+            (None, _) => {}
+        }
+        if let Some(solve) = &report.bench {
+            println!("  solve ({} runs): {solve}", solve.runs);
+        }
+        if let Some(usage) = &report.mem {
+            println!("  memory: {usage}");
+        }
+        return;
+    }
+
+    let mut fields = vec![
+        ("day", day.name.into()),
+        ("part", report.part.into()),
+        (
+            "answer",
+            report
+                .answer
+                .as_ref()
+                .map_or(Json::Null, |a| Json::number_or_string(&answer::one_line(a))),
+        ),
+        ("error", report.answer.as_ref().err().map(|e| &e[..]).into()),
+        ("parse_ns", parsed.time.as_nanos().into()),
+        ("solve_ns", report.solve_time.as_nanos().into()),
+        ("expected_match", matches.into()),
+    ];
+    if reference {
+        fields.push(("reference", true.into()));
+    }
+    if let Some(verdict) = verdict {
+        fields.push(("check", verdict.name().to_lowercase().into()));
+    }
+    if let (Some(parse), Some(solve)) = (&parsed.mem, &report.mem) {
+        fields.push(("parse_mem", parse.json()));
+        fields.push(("mem", solve.json()));
+    }
+    if let (Some(parse), Some(solve)) = (&parsed.stats, &report.bench) {
+        fields.push(("runs", solve.runs.into()));
+        fields.push(("parse", parse.json()));
+        fields.push(("solve", solve.json()));
+    }
+    println!("{}", Json::object(fields));
+}
+
+/// Asks on stdin whether to record each answer, returning those left unrecorded.
+fn accept(
+    answers: &mut Answers,
+    input_name: &str,
+    unrecorded: Vec<(usize, String, Verdict)>,
+) -> io::Result<Vec<(usize, String, Verdict)>> {
+    let mut left = vec![];
+    for (part, answer, verdict) in unrecorded {
+        if answer.contains('\n') {
+            eprint!("Record this as part {part} of {input_name}?\n{answer}\n[y/N] ");
+        } else {
+            eprint!("Record {answer} as part {part} of {input_name}? [y/N] ");
+        }
+        let mut reply = String::new();
+        io::stdin().read_line(&mut reply)?;
+        if reply.trim().eq_ignore_ascii_case("y") {
+            answers.set(input_name, part, answer);
+        } else {
+            left.push((part, answer, verdict));
+        }
+    }
+    Ok(left)
+}
+
+/// Runs a day as its own binary, as told by the command line.
+pub fn run_day(day: &Day) -> ExitCode {
+    let mut args: Vec<String> = env::args().collect();
+    let program = args.remove(0);
+    let program = Path::new(&program)
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let options = match RunOptions::from_args(Path::new(day.manifest_dir), args.clone()) {
+        Ok(Command::Run(options)) => options,
+        Ok(Command::Help) => {
+            println!("usage: {program} [options]\n\n{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n\nusage: {program} [options]\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    if options.mem && !mem::enable() {
+        eprintln!("error: --mem needs `advent::mem::Counting` as the global allocator");
+        return ExitCode::from(2);
+    }
+    if options.watch {
+        args.retain(|a| a != "--watch");
+        if let Err(e) = watch::watch(day, &args) {
+            eprintln!("error: {e}");
+        }
+        return ExitCode::FAILURE;
+    }
+    let selected = |part: &usize| options.part.is_none_or(|p| p == *part);
+    if options.reference && !(1..=2).any(|p| selected(&p) && day.references[p - 1].is_some()) {
+        eprintln!("error: no reference implementation to run");
+        return ExitCode::from(2);
+    }
+
+    let result = options
+        .read_input()
+        .map_err(parse::Error::from)
+        .and_then(|input| {
+            if options.isolate {
+                return Ok(isolate::run_isolated(day, &options, &input)?);
+            }
+            let parsed = day.parse_input(&input, &options)?;
+            if let (Some(parse), false) = (&parsed.stats, options.json) {
+                println!("Parse ({} runs): {parse}", parse.runs);
+            }
+            if let (Some(usage), false) = (&parsed.mem, options.json) {
+                println!("Parse memory: {usage}");
+            }
+            let manifest_dir = Path::new(day.manifest_dir);
+            let input_name = options.input_name(manifest_dir).unwrap_or_default();
+            let mut answers = match options.check {
+                true => Some(Answers::load(manifest_dir)?),
+                false => None,
+            };
+            let mut failed = false;
+            let mut unrecorded = vec![];
+            for i in 0..day.parts.len() {
+                if selected(&(i + 1)) {
+                    let reference = options.reference && day.references[i].is_some();
+                    let report = day.part(i + 1, &options)(&*parsed.value, &options)?;
+                    let matches = day.check(options.input.as_deref(), &report);
+                    let verdict = answers.as_ref().zip(report.answer.as_ref().ok());
+                    let verdict = verdict.map(|(a, answer)| a.check(&input_name, i + 1, answer));
+                    print_report(
+                        day,
+                        &parsed,
+                        &report,
+                        matches,
+                        verdict.as_ref(),
+                        reference,
+                        options.json,
+                    );
+                    failed |= report.answer.is_err();
+                    if let (Some(verdict), Ok(answer)) = (verdict, report.answer) {
+                        if verdict != Verdict::Pass {
+                            unrecorded.push((i + 1, answer, verdict));
+                        }
+                    }
+                }
+            }
+            if let (true, Some(answers)) = (options.accept, &mut answers) {
+                unrecorded = accept(answers, &input_name, unrecorded)?;
+                answers.save(manifest_dir)?;
+            }
+            failed |= unrecorded
+                .iter()
+                .any(|(_, _, v)| matches!(v, Verdict::Fail(_)));
+            Ok(failed)
+        });
+    match result {
+        Ok(false) => ExitCode::SUCCESS,
+        Ok(true) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
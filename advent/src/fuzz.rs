@@ -0,0 +1,340 @@
+use crate::cli::Day;
+use crate::mem::{self, Exceeded};
+use crate::RunOptions;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, Once};
+use std::time::{Duration, Instant};
+use std::{fmt, fs, panic, thread};
+
+/// Runs a day on arbitrary bytes, as a fuzzer's entry point. Input that isn't UTF-8 or doesn't
+/// parse is fine, but anything that parses has to get through both parts without panicking, and
+/// within [`FuzzOptions::default`]'s budget and memory, which with [`mem::Counting`] as the global
+/// allocator are enforced by panicking. Returns whether the input parsed.
+pub fn run_input(day: &Day, data: &[u8]) -> bool {
+    let options = FuzzOptions::default();
+    run_limited(day, data, options.budget, options.memory)
+}
+
+fn run_limited(day: &Day, data: &[u8], time: Duration, memory: usize) -> bool {
+    mem::limited(time, memory, || {
+        let Ok(input) = std::str::from_utf8(data) else {
+            return false;
+        };
+        let Ok(parsed) = (day.parse)(input) else {
+            return false;
+        };
+        let options = RunOptions::default();
+        for run in &day.parts {
+            let _ = run(&*parsed, &options);
+        }
+        true
+    })
+}
+
+/// A small xorshift generator, so runs can be repeated from a seed.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n.max(1) as u64) as usize
+    }
+}
+
+const INTERESTING_NUMBERS: &[&str] = &["0", "1", "-1", "255", "65536", "2147483648", "-99999999"];
+
+/// Numbers for inputs a slow reference implementation has to get through.
+const SMALL_NUMBERS: &[&str] = &["0", "1", "2", "3", "10"];
+
+/// Changes `input` in one of a few ways that tend to keep it close to a day's format, swapping
+/// numbers for ones from `numbers`.
+fn mutate(rng: &mut Rng, input: &mut Vec<u8>, corpus: &[Vec<u8>], numbers: &[&str]) {
+    let pos = rng.below(input.len() + 1);
+    match rng.below(7) {
+        // Replace a byte with one seen elsewhere in the input.
+        0 if !input.is_empty() => {
+            let b = input[rng.below(input.len())];
+            let last = input.len() - 1;
+            input[pos.min(last)] = b;
+        }
+        // Insert or remove a byte.
+        1 => {
+            let b = input.get(rng.below(input.len())).copied().unwrap_or(b'\n');
+            input.insert(pos, b);
+        }
+        2 if !input.is_empty() => {
+            let last = input.len() - 1;
+            input.remove(pos.min(last));
+        }
+        // Replace a number with one likely to find edge cases.
+        3 => {
+            let start = (pos..input.len()).find(|&i| input[i].is_ascii_digit());
+            if let Some(start) = start {
+                let end = (start..input.len())
+                    .find(|&i| !input[i].is_ascii_digit())
+                    .unwrap_or(input.len());
+                let n = numbers[rng.below(numbers.len())];
+                input.splice(start..end, n.bytes());
+            }
+        }
+        // Duplicate, remove or swap whole lines.
+        4..=5 => {
+            let mut lines: Vec<Vec<u8>> = input.split(|&b| b == b'\n').map(Vec::from).collect();
+            let (a, b) = (rng.below(lines.len()), rng.below(lines.len()));
+            match rng.below(3) {
+                0 => lines.insert(a, lines[b].clone()),
+                1 if lines.len() > 1 => {
+                    lines.remove(a);
+                }
+                _ => lines.swap(a, b),
+            }
+            *input = lines.join(&b'\n');
+        }
+        // Splice in part of another input.
+        _ => {
+            let other = &corpus[rng.below(corpus.len())];
+            let start = rng.below(other.len());
+            let end = start + rng.below(other.len() - start + 1);
+            input.splice(
+                pos..(pos + end - start).min(input.len()),
+                other[start..end].to_vec(),
+            );
+        }
+    }
+}
+
+pub struct FuzzOptions {
+    /// Stop after this many runs, or else after `time`.
+    pub runs: Option<usize>,
+    pub time: Duration,
+    /// How long one input may take before it counts as a hang.
+    pub budget: Duration,
+    /// How many bytes one input may have allocated at once.
+    pub memory: usize,
+    pub seed: u64,
+}
+
+impl Default for FuzzOptions {
+    fn default() -> Self {
+        Self {
+            runs: None,
+            time: Duration::from_secs(10),
+            budget: Duration::from_secs(1),
+            memory: 1 << 30,
+            seed: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Failure {
+    Panic,
+    Hang,
+    /// Allocated more than [`FuzzOptions::memory`].
+    Alloc,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panic => write!(f, "panicked"),
+            Self::Hang => write!(f, "hung"),
+            Self::Alloc => write!(f, "allocated too much"),
+        }
+    }
+}
+
+pub struct Report {
+    pub runs: usize,
+    /// How many runs parsed, and so got as far as the parts.
+    pub parsed: usize,
+    pub corpus: usize,
+    /// What went wrong, and where the input that did it was saved.
+    pub failure: Option<(Failure, PathBuf)>,
+}
+
+/// The day's example files, and its input if `with_input`, as their names and contents.
+fn seed_files(dir: &Path, with_input: bool) -> Vec<(String, Vec<u8>)> {
+    let mut files: Vec<_> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| (e.file_name().to_string_lossy().into_owned(), e.path()))
+        .filter(|(name, _)| {
+            (with_input && name == "input.txt")
+                || (name.starts_with("example") && name.ends_with(".txt"))
+        })
+        .filter_map(|(name, path)| Some((name, fs::read(path).ok()?)))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Checks a part agrees with its reference implementation, for the tests `#[part_one(reference)]`
+/// and `#[part_two(reference)]` generate. `run` gives both answers for an input, or `None` if it
+/// doesn't parse. Every example file has to agree, then up to `count` inputs made by mutating
+/// them. Mutants needn't be valid puzzles, so one that makes either implementation panic is
+/// skipped; numbers in them are kept small so the reference stays quick.
+pub fn check_agreement<A: PartialEq + fmt::Debug>(
+    manifest_dir: &str,
+    part: usize,
+    count: usize,
+    run: impl Fn(&str) -> Option<(A, A)>,
+) {
+    let examples = seed_files(Path::new(manifest_dir), false);
+    assert!(
+        !examples.is_empty(),
+        "no example files to compare part {part} on"
+    );
+    for (name, input) in &examples {
+        let input = String::from_utf8_lossy(input);
+        if let Some((answer, reference)) = run(&input) {
+            assert_eq!(
+                answer, reference,
+                "part {part} disagrees with its reference on {name}"
+            );
+        }
+    }
+
+    let mut corpus: Vec<Vec<u8>> = examples.into_iter().map(|(_, input)| input).collect();
+    let max_len = corpus.iter().map(Vec::len).max().unwrap_or(0) * 2 + 64;
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    let mut compared = 0;
+    for _ in 0..count * 50 {
+        if compared == count {
+            break;
+        }
+        let mut input = corpus[rng.below(corpus.len())].clone();
+        for _ in 0..=rng.below(4) {
+            mutate(&mut rng, &mut input, &corpus, SMALL_NUMBERS);
+        }
+        input.truncate(max_len);
+        let Ok(text) = std::str::from_utf8(&input) else {
+            continue;
+        };
+        let Ok(Some((answer, reference))) = panic::catch_unwind(AssertUnwindSafe(|| run(text)))
+        else {
+            continue;
+        };
+        assert_eq!(
+            answer, reference,
+            "part {part} disagrees with its reference on the generated input:\n{text}"
+        );
+        compared += 1;
+        corpus.push(input);
+    }
+}
+
+/// The input being run, written out by the panic hook so it's saved even if the panic aborts.
+static CURRENT: Mutex<Option<(Vec<u8>, PathBuf)>> = Mutex::new(None);
+
+fn save_current() {
+    if let Ok(current) = CURRENT.try_lock() {
+        if let Some((input, path)) = &*current {
+            let _ = fs::write(path, input);
+            eprintln!("failing input saved to {}", path.display());
+        }
+    }
+}
+
+/// Mutates the day's input and example files, running each mutant through [`run_input`] on a
+/// worker thread until the runs or time run out or an input panics, hangs or allocates too much.
+/// Mutants that parse join the corpus. A mutant is stopped by [`mem::limited`] at its first
+/// allocation past the budget; one stuck in a loop that doesn't allocate is reported after twice
+/// the budget, and left running on the worker, as a thread can't be killed.
+pub fn fuzz(day: &'static Day, options: &FuzzOptions) -> Report {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            save_current();
+            default(info);
+        }));
+    });
+
+    let mut corpus: Vec<Vec<u8>> = seed_files(Path::new(day.manifest_dir), true)
+        .into_iter()
+        .map(|(_, input)| input)
+        .collect();
+    if corpus.is_empty() {
+        corpus.push(vec![]);
+    }
+    let max_len = corpus.iter().map(Vec::len).max().unwrap_or(0) * 2 + 64;
+    let failure_path = std::env::temp_dir().join(format!("advent-fuzz-{}.txt", day.name));
+
+    let (inputs, worker_inputs) = mpsc::channel::<Vec<u8>>();
+    let (worker_results, results) = mpsc::channel();
+    let (budget, memory) = (options.budget, options.memory);
+    thread::spawn(move || {
+        for input in worker_inputs {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                run_limited(day, &input, budget, memory)
+            }))
+            .map_err(|_| match mem::exceeded() {
+                Some(Exceeded::Time) => Failure::Hang,
+                Some(Exceeded::Memory) => Failure::Alloc,
+                None => Failure::Panic,
+            });
+            if worker_results.send(result).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut rng = Rng(options.seed.max(1));
+    let start = Instant::now();
+    let mut report = Report {
+        runs: 0,
+        parsed: 0,
+        corpus: corpus.len(),
+        failure: None,
+    };
+    while options
+        .runs
+        .map_or(start.elapsed() < options.time, |r| report.runs < r)
+    {
+        let mut input = corpus[rng.below(corpus.len())].clone();
+        for _ in 0..=rng.below(4) {
+            mutate(&mut rng, &mut input, &corpus, INTERESTING_NUMBERS);
+        }
+        input.truncate(max_len);
+
+        *CURRENT.lock().unwrap() = Some((input.clone(), failure_path.clone()));
+        inputs.send(input.clone()).expect("fuzz worker stopped");
+        report.runs += 1;
+        match results.recv_timeout(options.budget * 2) {
+            Ok(Ok(parsed)) => {
+                if parsed {
+                    report.parsed += 1;
+                    if corpus.len() < 1000 {
+                        corpus.push(input);
+                    }
+                }
+            }
+            Ok(Err(failure)) => {
+                report.failure = Some((failure, failure_path));
+                break;
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                report.failure = Some((Failure::Panic, failure_path));
+                break;
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                save_current();
+                report.failure = Some((Failure::Hang, failure_path));
+                break;
+            }
+        }
+    }
+    *CURRENT.lock().unwrap() = None;
+    report.corpus = corpus.len();
+    report
+}
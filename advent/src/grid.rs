@@ -0,0 +1,103 @@
+use crate::answer::Answer;
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid of cells, indexed by `(x, y)` from the top left.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, cell: T) -> Self {
+        Self {
+            width,
+            cells: vec![cell; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// A grid of the given rows, which must all be the same length.
+    pub fn from_rows<R: IntoIterator<Item = T>>(rows: impl IntoIterator<Item = R>) -> Self {
+        let mut width = None;
+        let mut cells = vec![];
+        for row in rows {
+            let start = cells.len();
+            cells.extend(row);
+            let len = cells.len() - start;
+            assert_eq!(*width.get_or_insert(len), len, "rows of different lengths");
+        }
+        Self {
+            width: width.unwrap_or(0),
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width).then(|| self.cells.get(y * self.width + x))?
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width).then(|| self.cells.get_mut(y * self.width + x))?
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).expect("position outside the grid")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y).expect("position outside the grid")
+    }
+}
+
+/// Cells that can be drawn as one character.
+pub trait Cell {
+    fn symbol(&self) -> char;
+}
+
+/// Lit cells are `#` and dark ones `.`, as the puzzles draw them.
+impl Cell for bool {
+    fn symbol(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+impl Cell for char {
+    fn symbol(&self) -> char {
+        *self
+    }
+}
+
+/// Grids render as one line of text per row.
+impl<T: Cell> Answer for Grid<T> {
+    fn render(&self) -> String {
+        let rows: Vec<String> = self
+            .rows()
+            .map(|row| row.iter().map(Cell::symbol).collect())
+            .collect();
+        rows.join("\n")
+    }
+}
@@ -0,0 +1,184 @@
+use crate::cli::Day;
+use crate::json::Json;
+use crate::RunOptions;
+use std::io::{self, Write as _};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How a part's child process ended, when it didn't exit normally.
+enum Crash {
+    Panic(String),
+    Signal(i32),
+    Timeout(Duration),
+}
+
+impl Crash {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Panic(_) => "panic",
+            Self::Signal(_) => "signal",
+            Self::Timeout(_) => "timeout",
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::Panic(m) => format!("panicked: {m}"),
+            Self::Signal(s) => format!("killed by signal {s}"),
+            Self::Timeout(t) => format!("timed out after {t:?}"),
+        }
+    }
+}
+
+/// The message of the first panic in a child's stderr, in the default hook's format.
+fn panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr.lines().skip_while(|l| !l.contains("panicked at"));
+    let location = lines.next()?;
+    let message: Vec<_> = lines
+        .take_while(|l| !l.starts_with("note:") && !l.starts_with("stack backtrace:"))
+        .collect();
+    if message.is_empty() {
+        // Older toolchains put the message on the same line as the location.
+        Some(location.split_once("panicked at ")?.1.to_owned())
+    } else {
+        Some(message.join("\n"))
+    }
+}
+
+#[cfg(unix)]
+fn signal(status: ExitStatus) -> Option<i32> {
+    std::os::unix::process::ExitStatusExt::signal(&status)
+}
+
+#[cfg(not(unix))]
+fn signal(_status: ExitStatus) -> Option<i32> {
+    None
+}
+
+fn child_args(day: &Day, options: &RunOptions, part: usize) -> Vec<String> {
+    // The child reads the same file, so it can still check known answers, or stdin from us.
+    let input = match &options.input {
+        Some(path) => path.display().to_string(),
+        None => "-".into(),
+    };
+    let mut args = vec!["--part".into(), part.to_string(), "--input".into(), input];
+    if options.json {
+        args.push("--json".into());
+    }
+    if options.check {
+        args.push("--check".into());
+    }
+    if options.mem {
+        args.push("--mem".into());
+    }
+    // A part without a reference runs as usual.
+    if options.reference && day.references[part - 1].is_some() {
+        args.push("--reference".into());
+    }
+    if let Some(runs) = options.bench {
+        args.extend(["--bench".into(), runs.to_string()]);
+    }
+    for (key, value) in &options.params {
+        args.extend(["--param".into(), format!("{key}={value}")]);
+    }
+    args
+}
+
+fn read_to_end(mut pipe: impl io::Read + Send + 'static) -> JoinHandle<io::Result<String>> {
+    thread::spawn(move || {
+        let mut s = String::new();
+        pipe.read_to_string(&mut s).map(|_| s)
+    })
+}
+
+fn wait(child: &mut Child, timeout: Option<Duration>) -> io::Result<Option<ExitStatus>> {
+    let Some(timeout) = timeout else {
+        return child.wait().map(Some);
+    };
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    child.kill()?;
+    child.wait()?;
+    Ok(None)
+}
+
+/// Runs one part in a child process running this same binary, so a panic, abort or hang only
+/// loses that part. Returns whether the part failed.
+fn run_child(day: &Day, options: &RunOptions, input: &str, part: usize) -> io::Result<bool> {
+    let from_stdin = options.input.is_none();
+    let mut child = Command::new(std::env::current_exe()?)
+        .args(child_args(day, options, part))
+        .env("RUST_BACKTRACE", "0")
+        .stdin(if from_stdin {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Write and read on other threads so a child blocked on a full pipe can't stall us.
+    let writer = child.stdin.take().map(|mut stdin| {
+        let input = input.to_owned();
+        thread::spawn(move || stdin.write_all(input.as_bytes()))
+    });
+    let out = read_to_end(child.stdout.take().unwrap());
+    let err = read_to_end(child.stderr.take().unwrap());
+
+    let status = wait(&mut child, options.timeout)?;
+    if let Some(writer) = writer {
+        // A child that exits without reading all its input closes the pipe, which is fine.
+        let _ = writer.join().unwrap();
+    }
+    let stdout = out.join().unwrap()?;
+    let stderr = err.join().unwrap()?;
+
+    let crash = match status {
+        // Whatever a timed out part printed is incomplete, so drop it.
+        None => Crash::Timeout(options.timeout.unwrap()),
+        Some(status) => {
+            print!("{stdout}");
+            match (panic_message(&stderr), signal(status)) {
+                (Some(message), _) => Crash::Panic(message),
+                (None, Some(signal)) => Crash::Signal(signal),
+                (None, None) => {
+                    eprint!("{stderr}");
+                    return Ok(!status.success());
+                }
+            }
+        }
+    };
+
+    if options.json {
+        let record = Json::object([
+            ("day", day.name.into()),
+            ("part", part.into()),
+            ("answer", Json::Null),
+            ("error", crash.message().into()),
+            ("crash", crash.kind().into()),
+            ("expected_match", Json::Null),
+        ]);
+        println!("{record}");
+    } else {
+        eprintln!("Part {part} {}", crash.message());
+    }
+    Ok(true)
+}
+
+/// Runs the selected parts each in their own process. Returns whether any failed.
+pub fn run_isolated(day: &Day, options: &RunOptions, input: &str) -> io::Result<bool> {
+    let mut failed = false;
+    for part in [1, 2] {
+        if options.part.is_none_or(|p| p == part) {
+            failed |= run_child(day, options, input, part)?;
+        }
+    }
+    Ok(failed)
+}
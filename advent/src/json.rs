@@ -0,0 +1,320 @@
+use std::fmt;
+
+/// Just enough JSON to write machine readable results, one value per line, and read them back.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    /// Kept as text so integers of any size are written exactly.
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object<'a>(fields: impl IntoIterator<Item = (&'a str, Json)>) -> Self {
+        Self::Object(fields.into_iter().map(|(k, v)| (k.to_owned(), v)).collect())
+    }
+
+    /// The value of a field of an object.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Self::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// The text of a string or number.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Number(s) | Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut chars = s.trim().chars().peekable();
+        let value = parse_value(&mut chars)?;
+        match chars.next() {
+            None => Ok(value),
+            Some(c) => Err(format!("unexpected {c:?} after the value")),
+        }
+    }
+
+    /// A number when `s` is an integer written the way JSON writes it, otherwise a string, so
+    /// answers like `007` or `+5` keep their text.
+    pub fn number_or_string(s: &str) -> Self {
+        let canonical = match s.parse::<i128>() {
+            Ok(n) => Some(n.to_string()),
+            Err(_) => s.parse::<u128>().ok().map(|n| n.to_string()),
+        };
+        if canonical.as_deref() == Some(s) {
+            Self::Number(s.to_owned())
+        } else {
+            Self::String(s.to_owned())
+        }
+    }
+}
+
+macro_rules! json_from_number {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Json {
+            fn from(n: $t) -> Self {
+                Self::Number(n.to_string())
+            }
+        })*
+    }
+}
+
+json_from_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<bool> for Json {
+    fn from(b: bool) -> Self {
+        Self::Bool(b)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Self {
+        Self::String(s.to_owned())
+    }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(v: Option<T>) -> Self {
+        v.map(Into::into).unwrap_or(Self::Null)
+    }
+}
+
+type Chars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+fn skip_space(chars: &mut Chars) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+fn expect(chars: &mut Chars, expected: char) -> Result<(), String> {
+    skip_space(chars);
+    match chars.next() {
+        Some(c) if c == expected => Ok(()),
+        c => Err(format!("expected {expected:?}, found {c:?}")),
+    }
+}
+
+fn parse_value(chars: &mut Chars) -> Result<Json, String> {
+    skip_space(chars);
+    let value = match chars.peek() {
+        Some('"') => Json::String(parse_str(chars)?),
+        Some('[') => {
+            chars.next();
+            let mut items = vec![];
+            skip_space(chars);
+            if chars.next_if_eq(&']').is_none() {
+                loop {
+                    items.push(parse_value(chars)?);
+                    skip_space(chars);
+                    match chars.next() {
+                        Some(',') => continue,
+                        Some(']') => break,
+                        c => return Err(format!("expected ',' or ']', found {c:?}")),
+                    }
+                }
+            }
+            Json::Array(items)
+        }
+        Some('{') => {
+            chars.next();
+            let mut fields = vec![];
+            skip_space(chars);
+            if chars.next_if_eq(&'}').is_none() {
+                loop {
+                    skip_space(chars);
+                    let key = parse_str(chars)?;
+                    expect(chars, ':')?;
+                    fields.push((key, parse_value(chars)?));
+                    skip_space(chars);
+                    match chars.next() {
+                        Some(',') => continue,
+                        Some('}') => break,
+                        c => return Err(format!("expected ',' or '}}', found {c:?}")),
+                    }
+                }
+            }
+            Json::Object(fields)
+        }
+        _ => {
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || "+-.".contains(*c)) {
+                word.push(c);
+            }
+            match &word[..] {
+                "null" => Json::Null,
+                "true" => Json::Bool(true),
+                "false" => Json::Bool(false),
+                n if n.parse::<f64>().is_ok() => Json::Number(word),
+                _ => return Err(format!("unexpected {word:?}")),
+            }
+        }
+    };
+    skip_space(chars);
+    Ok(value)
+}
+
+fn parse_str(chars: &mut Chars) -> Result<String, String> {
+    expect(chars, '"')?;
+    let mut s = String::new();
+    loop {
+        match chars.next().ok_or("unterminated string")? {
+            '"' => return Ok(s),
+            '\\' => match chars.next().ok_or("unterminated string")? {
+                'n' => s.push('\n'),
+                'r' => s.push('\r'),
+                't' => s.push('\t'),
+                'b' => s.push('\u{8}'),
+                'f' => s.push('\u{c}'),
+                'u' => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    let c = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                    s.push(c.ok_or(format!("bad escape \\u{hex}"))?);
+                }
+                c => s.push(c),
+            },
+            c => s.push(c),
+        }
+    }
+}
+
+fn write_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    f.write_str("\"")
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("null"),
+            Self::Bool(b) => write!(f, "{b}"),
+            Self::Number(n) => f.write_str(n),
+            Self::String(s) => write_str(f, s),
+            Self::Array(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_str("]")
+            }
+            Self::Object(fields) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write_str(f, key)?;
+                    write!(f, ": {value}")?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer_record(answer: &str) -> Json {
+        Json::object([
+            ("part", 2.into()),
+            ("answer", Json::number_or_string(answer)),
+        ])
+    }
+
+    #[test]
+    fn integers_are_numbers() {
+        for n in ["0", "42", "-7", "340282366920938463463374607431768211455"] {
+            assert_eq!(Json::number_or_string(n), Json::Number(n.into()), "{n}");
+        }
+    }
+
+    #[test]
+    fn other_answers_are_strings() {
+        for s in [
+            "007",
+            "+5",
+            "-0",
+            "1e3",
+            "1.5",
+            "",
+            " 12",
+            "340282366920938463463374607431768211456",
+            "ECZUZALR",
+        ] {
+            assert_eq!(Json::number_or_string(s), Json::String(s.into()), "{s:?}");
+        }
+        assert_eq!(
+            answer_record("007").to_string(),
+            r#"{"part": 2, "answer": "007"}"#
+        );
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        let record = answer_record("say \"hi\" \\ to\ttab\r\u{1}é");
+        assert_eq!(
+            record.to_string(),
+            r#"{"part": 2, "answer": "say \"hi\" \\ to\ttab\r\u0001é"}"#
+        );
+        assert_eq!(Json::parse(&record.to_string()), Ok(record));
+    }
+
+    #[test]
+    fn multi_line_answers_stay_on_one_line() {
+        let crt = "\n##..##..\n###...##\n";
+        let written = answer_record(crt).to_string();
+        assert_eq!(
+            written,
+            r#"{"part": 2, "answer": "\n##..##..\n###...##\n"}"#
+        );
+        assert!(!written.contains('\n'));
+        let read = Json::parse(&written).unwrap();
+        assert_eq!(read.get("answer").and_then(Json::as_str), Some(crt));
+    }
+
+    #[test]
+    fn parses_what_it_writes() {
+        let record = Json::object([
+            ("day", "ten".into()),
+            ("answer", Json::Null),
+            ("expected_match", true.into()),
+            ("times", Json::Array(vec![1.into(), (-2).into()])),
+            ("error", "quoted \"answer\"".into()),
+        ]);
+        assert_eq!(Json::parse(&record.to_string()), Ok(record));
+        assert_eq!(
+            Json::parse(r#""\b\f\/é""#),
+            Ok(Json::String("\u{8}\u{c}/é".into()))
+        );
+        assert!(Json::parse(r#"{"a": 1"#).is_err());
+        assert!(Json::parse(r#""unterminated"#).is_err());
+    }
+}
@@ -1,6 +1,153 @@
+#![feature(alloc_error_hook)]
+
+use std::any::Any;
+use std::fmt;
+use std::time::Duration;
+
+pub mod answer;
+pub mod answers;
+pub mod bench;
+mod cli;
+pub mod fuzz;
+pub mod grid;
+mod isolate;
+pub mod json;
+pub mod mem;
+pub mod ocr;
+mod watch;
+
 pub mod prelude {
+    pub use super::{Grid, Params};
+    #[doc(hidden)]
+    pub use super::{_REFERENCE_PART_1, _REFERENCE_PART_2};
     pub use advent_macro::*;
     pub use parse::prelude::*;
 }
 pub use combine;
 pub use parse;
+
+pub use answer::{assert_answer, Answer};
+pub use cli::{run_day, Day, RunOptions};
+pub use grid::Grid;
+
+/// The parse trampoline generated by `harness!`, parsing input shared by both parts.
+pub type ParseInput = fn(&str) -> parse::Result<Box<dyn Any>>;
+
+/// A part trampoline generated by `#[part_one]` or `#[part_two]`, given the parsed input.
+pub type RunPart = fn(&dyn Any, &RunOptions) -> parse::Result<PartReport>;
+
+/// The reference implementations `harness!` registers for a day without any. A day's
+/// `#[part_one(reference)]` or `#[part_two(reference)]` defines its own, which takes precedence
+/// over these from the prelude's glob import.
+#[doc(hidden)]
+pub const _REFERENCE_PART_1: Option<RunPart> = None;
+#[doc(hidden)]
+pub const _REFERENCE_PART_2: Option<RunPart> = None;
+
+/// Constants a part needs that differ between the example and the real input. The defaults are
+/// for the real input, and `--param key=value` overrides them.
+pub trait Params: Default {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+}
+
+impl Params for () {
+    fn set(&mut self, key: &str, _value: &str) -> Result<(), String> {
+        Err(format!("unknown parameter `{key}`"))
+    }
+}
+
+pub fn build_params<P: Params>(pairs: &[(String, String)]) -> parse::Result<P> {
+    let mut params = P::default();
+    for (key, value) in pairs {
+        params.set(key, value).map_err(parse::Error::ParseError)?;
+    }
+    Ok(params)
+}
+
+/// A day's puzzle, implemented by `harness!` for the day's `Puzzle` type from its parts, so
+/// other tools can run days directly.
+pub trait Solution {
+    type Input;
+    /// The params both parts are given, `()` when neither takes any.
+    type Params: Params;
+    /// What `part_one` returns, which may be a `Result`.
+    type Answer1;
+    type Answer2;
+
+    fn parse(input: &str) -> parse::Result<Self::Input>;
+    fn part_one(input: &Self::Input, params: Self::Params) -> Self::Answer1;
+    fn part_two(input: &Self::Input, params: Self::Params) -> Self::Answer2;
+}
+
+/// Stands in for the params of a part that doesn't take any.
+#[doc(hidden)]
+pub struct NoParams;
+
+/// The params type shared by a part taking `Self` and a part taking `P`.
+#[doc(hidden)]
+pub trait JoinParams<P> {
+    type Output: Params;
+}
+
+impl JoinParams<NoParams> for NoParams {
+    type Output = ();
+}
+
+impl<P: Params> JoinParams<NoParams> for P {
+    type Output = P;
+}
+
+impl<P: Params> JoinParams<P> for NoParams {
+    type Output = P;
+}
+
+impl<P: Params> JoinParams<P> for P {
+    type Output = P;
+}
+
+/// Input types a day can start from. Anything with a parser is one, and `#[parse_input]` adds
+/// types built from a parsed value.
+pub trait FromInput: Sized {
+    fn from_input(input: &str) -> parse::Result<Self>;
+
+    /// Builds the input after normalising it, as `harness!` does for the day's input and examples.
+    fn from_input_with(options: &parse::Normalise, input: &str) -> parse::Result<Self> {
+        Self::from_input(&options.apply(input))
+    }
+}
+
+impl<T: parse::HasParser + parse::HasGrammar> FromInput for T {
+    fn from_input(input: &str) -> parse::Result<Self> {
+        parse::parse_str(input).map_err(|e| parse::Error::with_grammar::<T>(input, e))
+    }
+}
+
+/// What running one part produced.
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: usize,
+    /// The answer as rendered, or the error the part returned.
+    pub answer: Result<String, String>,
+    pub solve_time: Duration,
+    /// Solve timings, when benchmarking.
+    pub bench: Option<bench::Stats>,
+    pub mem: Option<mem::Usage>,
+}
+
+/// Solves one part from the shared parsed input. `prepare` runs untimed before each solve, to
+/// clone the input for parts that take it by value. Parts that can't fail are wrapped in `Ok`.
+pub fn run_part<T, R: Answer, E: fmt::Display>(
+    part: usize,
+    options: &RunOptions,
+    prepare: impl FnMut() -> parse::Result<T>,
+    solve: impl FnMut(T) -> Result<R, E>,
+) -> parse::Result<PartReport> {
+    let m = bench::measure(options.bench, prepare, solve)?;
+    Ok(PartReport {
+        part,
+        answer: m.value.map(|v| v.render()).map_err(|e| e.to_string()),
+        solve_time: m.time,
+        bench: m.stats,
+        mem: m.mem,
+    })
+}
@@ -0,0 +1,318 @@
+use crate::json::Json;
+use std::alloc::{self, GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering::Relaxed};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+use std::{fmt, fs, ptr};
+
+/// The system allocator, counting allocations once [`enable`]d and enforcing [`limited`]'s
+/// limits. Days' binaries install it:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: advent::mem::Counting = advent::mem::Counting;
+/// ```
+pub struct Counting;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+/// Bytes allocated and not yet freed since counting began, so frees of earlier allocations can
+/// take it below zero.
+static LIVE: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+fn grow(bytes: usize) {
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    ALLOCATED.fetch_add(bytes, Relaxed);
+    let live = LIVE.fetch_add(bytes as isize, Relaxed) + bytes as isize;
+    PEAK.fetch_max(live, Relaxed);
+}
+
+#[derive(Clone, Copy)]
+struct Limit {
+    deadline: Option<Instant>,
+    bytes: usize,
+    /// Bytes allocated on the thread and not yet freed since the limit was set.
+    live: isize,
+}
+
+thread_local! {
+    /// The thread's limit while it's in [`limited`]. Taken away when it's gone over, so the panic
+    /// that stops the run can allocate.
+    static LIMIT: Cell<Option<Limit>> = const { Cell::new(None) };
+    static EXCEEDED: Cell<Option<Exceeded>> = const { Cell::new(None) };
+}
+
+/// Counts `bytes` more against the thread's limit, if it has one, returning whether it's still
+/// within it.
+fn charge(bytes: isize) -> bool {
+    LIMIT
+        .try_with(|limit| {
+            let Some(mut l) = limit.get() else {
+                return true;
+            };
+            l.live += bytes;
+            let exceeded = if bytes <= 0 {
+                None
+            } else if usize::try_from(l.live).is_ok_and(|live| live > l.bytes) {
+                Some(Exceeded::Memory)
+            } else if l.deadline.is_some_and(|d| Instant::now() > d) {
+                Some(Exceeded::Time)
+            } else {
+                None
+            };
+            limit.set(exceeded.is_none().then_some(l));
+            EXCEEDED.set(exceeded.or(EXCEEDED.get()));
+            exceeded.is_none()
+        })
+        .unwrap_or(true)
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if !charge(layout.size() as isize) {
+            return ptr::null_mut();
+        }
+        if ENABLED.load(Relaxed) {
+            grow(layout.size());
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if !charge(layout.size() as isize) {
+            return ptr::null_mut();
+        }
+        if ENABLED.load(Relaxed) {
+            grow(layout.size());
+        }
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        charge(-(layout.size() as isize));
+        if ENABLED.load(Relaxed) {
+            LIVE.fetch_sub(layout.size() as isize, Relaxed);
+        }
+        System.dealloc(ptr, layout)
+    }
+
+    /// Counted as an allocation of the new size, less the old one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if !charge(new_size as isize - layout.size() as isize) {
+            return ptr::null_mut();
+        }
+        if ENABLED.load(Relaxed) {
+            LIVE.fetch_sub(layout.size() as isize, Relaxed);
+            grow(new_size);
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// What a [`limited`] run went over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Exceeded {
+    Time,
+    Memory,
+}
+
+impl fmt::Display for Exceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Time => write!(f, "went over its time limit"),
+            Self::Memory => write!(f, "went over its memory limit"),
+        }
+    }
+}
+
+/// Runs `f`, stopping it with a panic if it allocates on this thread once it's run for longer
+/// than `time`, or so as to have more than `bytes` allocated. [`Counting`] does the checking, so
+/// without it as the global allocator nothing is limited, and a loop that doesn't allocate
+/// can't be stopped.
+pub fn limited<T>(time: Duration, bytes: usize, f: impl FnOnce() -> T) -> T {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        alloc::set_alloc_error_hook(|layout| match EXCEEDED.get() {
+            Some(exceeded) => panic!("{exceeded}"),
+            // Returning aborts, as without the hook.
+            None => eprintln!("memory allocation of {} bytes failed", layout.size()),
+        })
+    });
+
+    struct Restore(Option<Limit>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            LIMIT.set(self.0);
+        }
+    }
+    let limit = Limit {
+        deadline: Instant::now().checked_add(time),
+        bytes,
+        live: 0,
+    };
+    EXCEEDED.set(None);
+    let _restore = Restore(LIMIT.replace(Some(limit)));
+    f()
+}
+
+/// What the last [`limited`] run on this thread went over, if it was stopped.
+pub fn exceeded() -> Option<Exceeded> {
+    EXCEEDED.get()
+}
+
+/// Starts counting, returning whether [`Counting`] is the global allocator.
+pub fn enable() -> bool {
+    ENABLED.store(true, Relaxed);
+    let before = ALLOCATIONS.load(Relaxed);
+    drop(std::hint::black_box(Box::new(0u64)));
+    ALLOCATIONS.load(Relaxed) != before
+}
+
+/// Resets the process's peak resident set size to what's resident now, returning whether it could.
+fn reset_peak_resident() -> bool {
+    cfg!(target_os = "linux") && fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+/// The process's peak resident set size since it was last reset, from `VmHWM`.
+fn peak_resident() -> Option<usize> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find_map(|l| l.strip_prefix("VmHWM:"))?;
+    let kib: usize = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// The memory used by one phase of a run.
+#[derive(Clone, Copy, Debug)]
+pub struct Usage {
+    /// The most heap bytes allocated at once, including what was already allocated when it
+    /// started.
+    pub peak_heap: usize,
+    /// The most memory the process had resident, where the OS can say.
+    pub peak_resident: Option<usize>,
+    pub allocations: usize,
+    pub allocated: usize,
+}
+
+impl Usage {
+    pub fn json(&self) -> Json {
+        Json::object([
+            ("peak_heap_bytes", self.peak_heap.into()),
+            ("peak_resident_bytes", self.peak_resident.into()),
+            ("allocations", self.allocations.into()),
+            ("allocated_bytes", self.allocated.into()),
+        ])
+    }
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "peak heap {}", Bytes(self.peak_heap))?;
+        if let Some(resident) = self.peak_resident {
+            write!(f, ", peak resident {}", Bytes(resident))?;
+        }
+        write!(
+            f,
+            ", {} allocations of {}",
+            self.allocations,
+            Bytes(self.allocated)
+        )
+    }
+}
+
+struct Bytes(usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut size = self.0 as f64;
+        for unit in ["B", "KiB", "MiB"] {
+            if size < 1024.0 {
+                return match unit {
+                    "B" => write!(f, "{} B", self.0),
+                    unit => write!(f, "{size:.1} {unit}"),
+                };
+            }
+            size /= 1024.0;
+        }
+        write!(f, "{size:.1} GiB")
+    }
+}
+
+/// Runs `f`, with what it allocated if counting is enabled.
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    if !ENABLED.load(Relaxed) {
+        return (f(), None);
+    }
+    let resident = reset_peak_resident();
+    let (allocations, allocated) = (ALLOCATIONS.load(Relaxed), ALLOCATED.load(Relaxed));
+    PEAK.store(LIVE.load(Relaxed), Relaxed);
+    let t = f();
+    let mut usage = Usage {
+        peak_heap: PEAK.load(Relaxed).max(0) as usize,
+        peak_resident: None,
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        allocated: ALLOCATED.load(Relaxed) - allocated,
+    };
+    // Read after the counts, as reading it allocates.
+    usage.peak_resident = peak_resident().filter(|_| resident);
+    (t, Some(usage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    fn runs_within_limits() {
+        let v = limited(Duration::from_secs(10), 1 << 20, || vec![1u8; 1000]);
+        assert_eq!(v.len(), 1000);
+        assert_eq!(exceeded(), None);
+        // Freeing what was allocated before doesn't make room.
+        let earlier = vec![0u8; 1 << 20];
+        let n = limited(Duration::from_secs(10), 1 << 10, || {
+            drop(earlier);
+            vec![0u8; 100].len()
+        });
+        assert_eq!(n, 100);
+        // Unlimited again afterwards.
+        assert_eq!(vec![0u8; 2 << 20].len(), 2 << 20);
+    }
+
+    #[test]
+    fn stops_runs_that_allocate_too_much() {
+        let result = panic::catch_unwind(|| {
+            limited(Duration::from_secs(10), 1 << 20, || {
+                let kept: Vec<Vec<u8>> = (0..100).map(|_| vec![0; 100_000]).collect();
+                kept.len()
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(exceeded(), Some(Exceeded::Memory));
+        // Freed memory doesn't count.
+        let n = limited(Duration::from_secs(10), 1 << 20, || {
+            (0..100).map(|_| vec![0u8; 100_000].len()).sum::<usize>()
+        });
+        assert_eq!(n, 10_000_000);
+    }
+
+    #[test]
+    fn stops_runs_that_allocate_too_long() {
+        let result = panic::catch_unwind(|| {
+            limited(Duration::from_millis(10), usize::MAX, || loop {
+                std::hint::black_box(vec![0u8; 16]);
+            })
+        });
+        let message = result.unwrap_err();
+        assert_eq!(
+            message.downcast_ref::<String>().map(String::as_str),
+            Some("went over its time limit")
+        );
+        assert_eq!(exceeded(), Some(Exceeded::Time));
+    }
+}
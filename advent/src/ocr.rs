@@ -0,0 +1,342 @@
+use crate::answer::{self, Answer};
+use crate::grid::Grid;
+use std::fmt;
+
+/// The small font, 4 wide and 6 tall with a blank column between letters.
+const SMALL: Font = Font {
+    width: 4,
+    height: 6,
+    gap: 1,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Y', &["#...", "#...", ".#.#", "..#.", "..#.", "..#."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+/// The large font, 6 wide and 10 tall with two blank columns between letters.
+const LARGE: Font = Font {
+    width: 6,
+    height: 10,
+    gap: 2,
+    glyphs: &[
+        (
+            'A',
+            &[
+                "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'B',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+                "#....#", "#####.",
+            ],
+        ),
+        (
+            'C',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#....#", ".####.",
+            ],
+        ),
+        (
+            'E',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'F',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'G',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+                "#...##", ".###.#",
+            ],
+        ),
+        (
+            'H',
+            &[
+                "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'J',
+            &[
+                "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+                "#...#.", ".###..",
+            ],
+        ),
+        (
+            'K',
+            &[
+                "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+                "#...#.", "#....#",
+            ],
+        ),
+        (
+            'L',
+            &[
+                "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'N',
+            &[
+                "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+                "#...##", "#....#",
+            ],
+        ),
+        (
+            'P',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'R',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'X',
+            &[
+                "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'Z',
+            &[
+                "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+    ],
+};
+
+struct Font {
+    width: usize,
+    height: usize,
+    gap: usize,
+    /// Each letter's rows.
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Neither font is this many rows tall.
+    Height(usize),
+    /// The glyph at this position, counting from 0, isn't a letter of the font.
+    UnknownGlyph(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Height(h) => write!(f, "expected letters 6 or 10 rows tall, found {h} rows"),
+            Self::UnknownGlyph(i) => write!(f, "glyph {} isn't a known letter", i + 1),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Reads the letters drawn in `#`s, with anything else dark.
+pub fn read(drawing: &str) -> Result<String, Error> {
+    let drawing = answer::normalise(drawing);
+    let rows: Vec<Vec<bool>> = drawing
+        .lines()
+        .map(|l| l.chars().map(|c| c == '#').collect())
+        .collect();
+    let font = [SMALL, LARGE]
+        .into_iter()
+        .find(|f| f.height == rows.len())
+        .ok_or(Error::Height(rows.len()))?;
+
+    // Blank columns either side are margin, so letters are found from the first lit column. Only a
+    // letter with blank columns of its own on the left, like the small `I`, can start before it.
+    let lit_columns = (0..rows.iter().map(Vec::len).max().unwrap_or(0))
+        .filter(|&x| rows.iter().any(|row| row.get(x) == Some(&true)));
+    let (Some(first), Some(last)) = (lit_columns.clone().min(), lit_columns.max()) else {
+        return Ok(String::new());
+    };
+    let first = first as isize;
+    let mut result = read_from(&rows, &font, first, last);
+    for shift in 1..font.width as isize {
+        if result.is_ok() {
+            break;
+        }
+        result = read_from(&rows, &font, first - shift, last).or(result);
+    }
+    result
+}
+
+/// Reads the letters of `font` whose left edges are every stride from column `left` up to column
+/// `last`, with columns off the drawing dark.
+fn read_from(rows: &[Vec<bool>], font: &Font, left: isize, last: usize) -> Result<String, Error> {
+    let lit = |x: isize, y: usize| {
+        usize::try_from(x).is_ok_and(|x| rows[y].get(x).copied().unwrap_or(false))
+    };
+    let stride = font.width + font.gap;
+    let mut letters = String::new();
+    for (i, left) in (left..=last as isize).step_by(stride).enumerate() {
+        let glyph: Vec<String> = (0..font.height)
+            .map(|y| {
+                (left..left + font.width as isize)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        if !glyph.iter().any(|row| row.contains('#')) {
+            continue;
+        }
+        let (letter, _) = font
+            .glyphs
+            .iter()
+            .find(|(_, g)| *g == glyph)
+            .ok_or(Error::UnknownGlyph(i))?;
+        letters.push(*letter);
+    }
+    Ok(letters)
+}
+
+pub fn read_grid(grid: &Grid<bool>) -> Result<String, Error> {
+    read(&grid.render())
+}
+
+/// An answer drawn as letters, which renders as the letters when they can be read and as the
+/// drawing otherwise. `#[part_one(ocr)]` and `#[part_two(ocr)]` turn answers into these.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Text {
+    drawing: String,
+    letters: Option<String>,
+}
+
+impl Text {
+    pub fn read(drawing: &impl Answer) -> Self {
+        let drawing = drawing.render();
+        Self {
+            letters: read(&drawing).ok(),
+            drawing,
+        }
+    }
+
+    pub fn letters(&self) -> Option<&str> {
+        self.letters.as_deref()
+    }
+
+    pub fn drawing(&self) -> &str {
+        &self.drawing
+    }
+}
+
+impl Answer for Text {
+    fn render(&self) -> String {
+        self.letters.clone().unwrap_or_else(|| self.drawing.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `letters` drawn in `font`, with `margin` blank columns either side.
+    fn draw(font: &Font, letters: &str, margin: usize) -> String {
+        let glyph = |c| font.glyphs.iter().find(|(l, _)| *l == c).unwrap().1;
+        (0..font.height)
+            .map(|y| {
+                let row: Vec<&str> = letters.chars().map(|c| glyph(c)[y]).collect();
+                let blank = ".".repeat(margin);
+                blank.clone() + &row.join(&".".repeat(font.gap)) + &blank + "\n"
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_small_letters() {
+        assert_eq!(
+            read(
+                "\
+###..#..#.####.#....
+#..#.#..#.#....#....
+###..####.###..#....
+#..#.#..#.#....#....
+#..#.#..#.#....#....
+###..#..#.####.####."
+            ),
+            Ok("BHEL".into())
+        );
+        let all: String = SMALL.glyphs.iter().map(|(c, _)| c).collect();
+        assert_eq!(read(&draw(&SMALL, &all, 0)), Ok(all));
+    }
+
+    #[test]
+    fn reads_large_letters() {
+        let all: String = LARGE.glyphs.iter().map(|(c, _)| c).collect();
+        assert_eq!(read(&draw(&LARGE, &all, 0)), Ok(all));
+        assert_eq!(read(&draw(&LARGE, "ZX", 3)), Ok("ZX".into()));
+    }
+
+    #[test]
+    fn ignores_blank_columns_either_side() {
+        assert_eq!(read(&draw(&SMALL, "HI", 3)), Ok("HI".into()));
+        assert_eq!(read(&draw(&SMALL, "JAZZ", 2)), Ok("JAZZ".into()));
+        // The small `I`'s own blank column trimmed off along with the margin.
+        let trimmed: String = draw(&SMALL, "IF", 0)
+            .lines()
+            .map(|l| l[1..].to_owned() + "\n")
+            .collect();
+        assert_eq!(read(&trimmed), Ok("IF".into()));
+    }
+
+    #[test]
+    fn reports_unknown_glyphs() {
+        let mut drawing = draw(&SMALL, "AB", 1);
+        // Fills in the middle of the `B`.
+        drawing.replace_range(8..11, "###");
+        assert_eq!(read(&drawing), Err(Error::UnknownGlyph(1)));
+        assert_eq!(
+            Error::UnknownGlyph(1).to_string(),
+            "glyph 2 isn't a known letter"
+        );
+    }
+
+    #[test]
+    fn reports_the_wrong_height() {
+        let drawing = draw(&SMALL, "A", 0) + "....\n";
+        assert_eq!(read(&drawing), Err(Error::Height(7)));
+        assert_eq!(
+            Error::Height(7).to_string(),
+            "expected letters 6 or 10 rows tall, found 7 rows"
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use crate::cli::Day;
+use crate::json::Json;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use std::{env, io};
+
+/// How long files have to stay unchanged before a change is acted on, so a burst of saves only
+/// rebuilds once.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Whether a change to the file named `name` in the day's directory or its `src/` matters.
+fn watched(name: &str) -> bool {
+    name.ends_with(".rs")
+        || name == "Cargo.toml"
+        || name == "input.txt"
+        || (name.starts_with("example") && name.ends_with(".txt"))
+}
+
+#[cfg(target_os = "linux")]
+mod changes {
+    use super::{watched, DEBOUNCE};
+    use inotify::{Inotify, WatchMask};
+    use std::path::Path;
+    use std::{io, thread};
+
+    /// Changes to the watched files, seen through inotify.
+    pub struct Changes {
+        inotify: Inotify,
+    }
+
+    impl Changes {
+        pub fn new(dirs: &[&Path]) -> io::Result<Self> {
+            let inotify = Inotify::init()?;
+            // Directories rather than files, as editors often save by replacing the file.
+            let mask = WatchMask::CLOSE_WRITE
+                | WatchMask::MODIFY
+                | WatchMask::CREATE
+                | WatchMask::DELETE
+                | WatchMask::MOVED_TO;
+            for dir in dirs {
+                inotify.watches().add(dir, mask)?;
+            }
+            Ok(Self { inotify })
+        }
+
+        /// Waits for a watched file to change and then for things to settle down.
+        pub fn wait(&mut self) -> io::Result<()> {
+            let mut buffer = [0; 4096];
+            loop {
+                let events = self.inotify.read_events_blocking(&mut buffer)?;
+                if events
+                    .filter_map(|e| e.name)
+                    .any(|name| watched(&name.to_string_lossy()))
+                {
+                    break;
+                }
+            }
+            loop {
+                thread::sleep(DEBOUNCE);
+                let settled = match self.inotify.read_events(&mut buffer) {
+                    Ok(events) => events.count() == 0,
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => true,
+                    Err(e) => return Err(e),
+                };
+                if settled {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod changes {
+    use super::{watched, DEBOUNCE};
+    use std::path::{Path, PathBuf};
+    use std::time::SystemTime;
+    use std::{fs, io, thread};
+
+    /// Changes to the watched files, seen by polling their modification times.
+    pub struct Changes {
+        dirs: Vec<PathBuf>,
+        last: Vec<(PathBuf, SystemTime)>,
+    }
+
+    impl Changes {
+        pub fn new(dirs: &[&Path]) -> io::Result<Self> {
+            let dirs: Vec<_> = dirs.iter().map(|d| d.to_path_buf()).collect();
+            let last = snapshot(&dirs);
+            Ok(Self { dirs, last })
+        }
+
+        pub fn wait(&mut self) -> io::Result<()> {
+            loop {
+                thread::sleep(DEBOUNCE);
+                let now = snapshot(&self.dirs);
+                if now != self.last {
+                    self.last = now;
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    fn snapshot(dirs: &[PathBuf]) -> Vec<(PathBuf, SystemTime)> {
+        let mut files: Vec<_> = dirs
+            .iter()
+            .flat_map(|d| fs::read_dir(d).into_iter().flatten().flatten())
+            .filter(|e| watched(&e.file_name().to_string_lossy()))
+            .filter_map(|e| Some((e.path(), e.metadata().ok()?.modified().ok()?)))
+            .collect();
+        files.sort();
+        files
+    }
+}
+
+/// What one part printed in a run, read from its `--json` output.
+struct PartRun {
+    part: String,
+    /// The answer, or the error prefixed with "failed: ".
+    answer: String,
+    solve: Duration,
+}
+
+/// Rebuilds the day's binary with cargo, showing the errors if it fails. Returns whether it
+/// built.
+fn build(day: &Day) -> io::Result<bool> {
+    let mut cargo = Command::new(env::var_os("CARGO").unwrap_or("cargo".into()));
+    cargo
+        .args(["build", "--message-format", "short", "--package", day.name])
+        .current_dir(day.manifest_dir);
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    let output = cargo.stdout(Stdio::null()).output()?;
+    if !output.status.success() {
+        println!("build failed:");
+        // Short messages are one line each, so this leaves just the errors.
+        for line in String::from_utf8_lossy(&output.stderr).lines() {
+            if line.contains("error") {
+                println!("  {line}");
+            }
+        }
+    }
+    Ok(output.status.success())
+}
+
+/// Runs the freshly built binary `exe` with `args`, reading what each part printed.
+fn run(exe: &Path, args: &[String]) -> io::Result<Vec<PartRun>> {
+    let output = Command::new(exe)
+        .args(args)
+        .arg("--json")
+        .stdin(Stdio::null())
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.trim().is_empty() {
+        print!("{stderr}");
+    }
+    let mut parts = vec![];
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Ok(record) = Json::parse(line) else {
+            println!("{line}");
+            continue;
+        };
+        let field = |key| record.get(key).and_then(Json::as_str).unwrap_or_default();
+        let answer = match (record.get("answer"), field("error")) {
+            (Some(Json::Null) | None, error) => format!("failed: {error}"),
+            (Some(answer), _) => answer.as_str().unwrap_or_default().to_owned(),
+        };
+        let solve = Duration::from_nanos(field("solve_ns").parse().unwrap_or(0));
+        let part = field("part").to_owned();
+        parts.push(PartRun {
+            part,
+            answer,
+            solve,
+        });
+    }
+    Ok(parts)
+}
+
+/// Prints a run's parts, with what changed since the previous run.
+fn print_changes(runs: &[PartRun], previous: &[PartRun]) {
+    for run in runs {
+        let before = previous.iter().find(|p| p.part == run.part);
+        let answer = match before {
+            Some(before) if before.answer != run.answer => {
+                format!("{} (was {})", run.answer, before.answer)
+            }
+            _ => run.answer.clone(),
+        };
+        let time = match before {
+            Some(before) if !before.solve.is_zero() => format!(
+                "{:.2?}, was {:.2?}, {:.2}x",
+                run.solve,
+                before.solve,
+                run.solve.as_secs_f64() / before.solve.as_secs_f64()
+            ),
+            _ => format!("{:.2?}", run.solve),
+        };
+        if answer.contains('\n') {
+            println!("Part {}:\n{answer}\n  ({time})", run.part);
+        } else {
+            println!("Part {}: {answer} ({time})", run.part);
+        }
+    }
+}
+
+/// Rebuilds and re-runs the day, with the command line's other `args`, whenever its source, input
+/// or examples change, until interrupted.
+pub fn watch(day: &Day, args: &[String]) -> io::Result<()> {
+    let dir = Path::new(day.manifest_dir);
+    let src: PathBuf = dir.join("src");
+    let mut changes = changes::Changes::new(&[dir, &src])?;
+    // Looked up before rebuilding, which replaces the running binary's file.
+    let exe = env::current_exe()?;
+    let mut previous = vec![];
+    loop {
+        println!("\n── {} ──", day.name);
+        if build(day)? {
+            let runs = run(&exe, args)?;
+            print_changes(&runs, &previous);
+            previous = runs;
+        }
+        changes.wait()?;
+    }
+}
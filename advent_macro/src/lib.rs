@@ -5,13 +5,43 @@ use proc_macro2::Span;
 use quote::quote;
 use syn::parse::Parse;
 use syn::parse::ParseStream;
+use syn::parse::Parser as _;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned as _;
 use syn::token::*;
 use syn::*;
 
-fn part_inner(input: TokenStream, part_number: usize) -> Result<File> {
+/// `T` when `ty` is spelled `Result<T, E>`.
+fn result_ok_type(ty: &syn::Type) -> Option<syn::Type> {
+    let syn::Type::Path(TypePath {
+        qself: None, path, ..
+    }) = ty
+    else {
+        return None;
+    };
+    let last = path.segments.last()?;
+    let PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+    match (last.ident == "Result", args.args.first(), args.args.len()) {
+        (true, Some(GenericArgument::Type(ok)), 2) => Some(ok.clone()),
+        _ => None,
+    }
+}
+
+fn part_inner(attr: TokenStream, input: TokenStream, part_number: usize) -> Result<File> {
     let func: ItemFn = parse(input)?;
+    // `ocr` reads answers drawn as letters, so they're shown and recorded as the letters.
+    // `reference` marks a slower implementation of a part, for the part to be tested against.
+    let (mut ocr, mut reference) = (false, false);
+    let flags = Punctuated::<Ident, Token![,]>::parse_terminated.parse(attr)?;
+    for flag in flags {
+        match &flag.to_string()[..] {
+            "ocr" => ocr = true,
+            "reference" => reference = true,
+            _ => return Err(Error::new(flag.span(), "expected `ocr` or `reference`")),
+        }
+    }
 
     let func_name = func.sig.ident.clone();
     let args = func.sig.inputs.clone();
@@ -21,69 +51,324 @@ fn part_inner(input: TokenStream, part_number: usize) -> Result<File> {
         parse_quote!(())
     };
 
-    if args.len() != 1 {
-        return Err(Error::new(args.span(), "too many inputs"));
+    // Parts returning `Result<T, E>` report the error, others are wrapped in `Ok`.
+    let (answer, fallible) = match result_ok_type(&ret) {
+        Some(ok) => (ok, true),
+        None => (ret.clone(), false),
+    };
+    let answer = match ocr {
+        true => parse_quote!(::advent::ocr::Text),
+        false => answer,
+    };
+    let wrap = |call: proc_macro2::TokenStream| match (fallible, ocr) {
+        (true, false) => call,
+        (true, true) => quote!(#call.map(|a| ::advent::ocr::Text::read(&a))),
+        (false, false) => {
+            quote!(::std::result::Result::<_, ::std::convert::Infallible>::Ok(#call))
+        }
+        (false, true) => quote! {
+            ::std::result::Result::<_, ::std::convert::Infallible>::Ok(
+                ::advent::ocr::Text::read(&#call),
+            )
+        },
+    };
+
+    if args.is_empty() || args.len() > 2 {
+        return Err(Error::new(
+            args.span(),
+            "expected an input and optional params",
+        ));
     }
-    let parsed_type = if let FnArg::Typed(pat_type) = args.first().unwrap() {
-        (&*pat_type.ty).clone()
-    } else {
-        return Err(Error::new(args.span(), "invalid input type"));
+    let mut arg_types = vec![];
+    for arg in &args {
+        if let FnArg::Typed(pat_type) = arg {
+            arg_types.push((*pat_type.ty).clone());
+        } else {
+            return Err(Error::new(arg.span(), "invalid input type"));
+        }
+    }
+
+    // Parts take the shared input by reference, or get their own clone of it.
+    let (input_type, input_arg, test_input_arg) = match &arg_types[0] {
+        syn::Type::Reference(r) => ((*r.elem).clone(), quote!(input), quote!(&input)),
+        ty => (
+            ty.clone(),
+            quote!(::std::clone::Clone::clone(input)),
+            quote!(input),
+        ),
+    };
+    let solution_call = match arg_types.len() {
+        1 => quote!(#func_name(#input_arg)),
+        _ if matches!(arg_types[1], syn::Type::Reference(_)) => {
+            quote!(#func_name(#input_arg, &params))
+        }
+        _ => quote!(#func_name(#input_arg, params)),
     };
 
+    // The params value passed to the part, and the type to build it from.
+    let params = arg_types.get(1).map(|ty| match ty {
+        syn::Type::Reference(r) => ((*r.elem).clone(), quote!(&params)),
+        ty => (ty.clone(), quote!(params)),
+    });
+    let (params_type, solution_params) = match &params {
+        Some((ty, _)) => (quote!(#ty), quote!(params: #ty)),
+        None => (quote!(::advent::NoParams), quote!(_params: impl Sized)),
+    };
+    let (call, test_call, build_params, take_params) = match params {
+        Some((params_type, arg)) => (
+            quote!(#func_name(p, #arg)),
+            quote!(#func_name(#test_input_arg, #arg)),
+            quote!(::advent::build_params::<#params_type>(&options.params)?),
+            quote! {
+                let params: #params_type = match params {
+                    Some(params) => *params.downcast().expect("params of the wrong type"),
+                    None => ::std::default::Default::default(),
+                };
+            },
+        ),
+        None => (
+            quote!(#func_name(p)),
+            quote!(#func_name(#test_input_arg)),
+            quote!(()),
+            quote!(),
+        ),
+    };
+
+    let call = wrap(call);
+    let test_call = wrap(test_call);
+
+    if reference {
+        return Ok(reference_part(
+            func,
+            part_number,
+            (input_type, input_arg, ret),
+            (params_type, solution_params, solution_call),
+            (build_params, call),
+        ));
+    }
+
+    let input_alias = Ident::new(&format!("_Part{}Input", part_number), Span::call_site());
+    let params_alias = Ident::new(&format!("_Part{}Params", part_number), Span::call_site());
+    let output_alias = Ident::new(&format!("_Part{}Output", part_number), Span::call_site());
+    let solution_tramp = Ident::new(&format!("_part_{}", part_number), Span::call_site());
     let tramp = Ident::new(&format!("_run_part_{}", part_number), Span::call_site());
     let test_tramp = Ident::new(&format!("_test_part_{}", part_number), Span::call_site());
 
     Ok(parse_quote! {
         #func
-        fn #tramp(input: &str, json: bool) -> ::advent::parse::Result<()> {
-            let p: #parsed_type = ::advent::parse::parse_str(input)?;
-            let result = #func_name(p);
-            if json {
-                println!("{{\"part\": {}, \"answer\": \"{}\"}}", #part_number, result);
-            } else {
-                println!("Part {}: {}", #part_number, result);
-            }
-            Ok(())
+        type #input_alias = #input_type;
+        type #params_alias = #params_type;
+        type #output_alias = #ret;
+
+        fn #solution_tramp(input: &#input_type, #solution_params) -> #ret {
+            #solution_call
+        }
+
+        fn #tramp(
+            input: &dyn ::std::any::Any,
+            options: &::advent::RunOptions,
+        ) -> ::advent::parse::Result<::advent::PartReport> {
+            let input: &#input_type = input.downcast_ref().expect("input of the wrong type");
+            ::advent::run_part(
+                #part_number,
+                options,
+                || Ok((#input_arg, #build_params)),
+                |(p, params)| {
+                    let _ = &params;
+                    #call
+                },
+            )
         }
 
         #[cfg(test)]
-        fn #test_tramp(input: &str) -> ::advent::parse::Result<#ret> {
-            let p: #parsed_type = ::advent::parse::parse_str(input)?;
-            Ok(#func_name(p))
+        fn #test_tramp(
+            input: &str,
+            params: ::std::option::Option<::std::boxed::Box<dyn ::std::any::Any>>,
+        ) -> ::advent::parse::Result<#answer> {
+            let _ = &params;
+            #take_params
+            let input: #input_type = ::advent::FromInput::from_input_with(&_normalise(), input)?;
+            match #test_call {
+                Ok(answer) => Ok(answer),
+                Err(e) => panic!("part {} failed: {}", #part_number, e),
+            }
         }
     })
 }
 
+/// The items for a part's reference implementation: trampolines like a part's, registered in
+/// `_REFERENCE_PART_N` for `harness!` to find, and a test that the part agrees with it.
+fn reference_part(
+    func: ItemFn,
+    part_number: usize,
+    (input_type, input_arg, ret): (syn::Type, proc_macro2::TokenStream, syn::Type),
+    (params_type, solution_params, solution_call): (
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+    ),
+    (build_params, call): (proc_macro2::TokenStream, proc_macro2::TokenStream),
+) -> File {
+    let ident = |s: String| Ident::new(&s, Span::call_site());
+    let part_input = ident(format!("_Part{part_number}Input"));
+    let part_params = ident(format!("_Part{part_number}Params"));
+    let part_tramp = ident(format!("_part_{part_number}"));
+    let params_alias = ident(format!("_Reference{part_number}Params"));
+    let solution_tramp = ident(format!("_reference_{part_number}"));
+    let tramp = ident(format!("_run_reference_{part_number}"));
+    let registration = ident(format!("_REFERENCE_PART_{part_number}"));
+    let test_name = ident(format!("part_{part_number}_agrees_with_reference"));
+
+    parse_quote! {
+        #func
+        type #params_alias = #params_type;
+
+        fn #solution_tramp(input: &#input_type, #solution_params) -> #ret {
+            #solution_call
+        }
+
+        fn #tramp(
+            input: &dyn ::std::any::Any,
+            options: &::advent::RunOptions,
+        ) -> ::advent::parse::Result<::advent::PartReport> {
+            let input: &#input_type = input.downcast_ref().expect("input of the wrong type");
+            ::advent::run_part(
+                #part_number,
+                options,
+                || Ok((#input_arg, #build_params)),
+                |(p, params)| {
+                    let _ = &params;
+                    #call
+                },
+            )
+        }
+
+        const #registration: ::std::option::Option<::advent::RunPart> = Some(#tramp);
+
+        #[test]
+        fn #test_name() {
+            type Params = <#part_params as ::advent::JoinParams<#params_alias>>::Output;
+            ::advent::fuzz::check_agreement(
+                ::std::env!("CARGO_MANIFEST_DIR"),
+                #part_number,
+                32,
+                |input| {
+                    let input: #part_input = ::advent::FromInput::from_input_with(&_normalise(), input).ok()?;
+                    let answer = #part_tramp(&input, <Params as ::std::default::Default>::default());
+                    let reference =
+                        #solution_tramp(&input, <Params as ::std::default::Default>::default());
+                    Some((answer, reference))
+                },
+            );
+        }
+    }
+}
+
+/// Marks a function building the input both parts take from a parsed value, so the work is done
+/// once.
 #[proc_macro_attribute]
-pub fn part_one(_attr: TokenStream, input: TokenStream) -> TokenStream {
-    match part_inner(input, 1) {
+pub fn parse_input(_attr: TokenStream, input: TokenStream) -> TokenStream {
+    match parse_input_inner(input) {
         Ok(v) => quote!(#v).into(),
         Err(e) => e.into_compile_error().into(),
     }
 }
 
+fn parse_input_inner(input: TokenStream) -> Result<File> {
+    let func: ItemFn = parse(input)?;
+    let func_name = &func.sig.ident;
+    let (FnArg::Typed(arg), 1) = (func.sig.inputs.first().unwrap(), func.sig.inputs.len()) else {
+        return Err(Error::new(
+            func.sig.inputs.span(),
+            "expected one parsed argument",
+        ));
+    };
+    let parsed_type = &arg.ty;
+    let ReturnType::Type(_, output) = &func.sig.output else {
+        return Err(Error::new(func.sig.span(), "expected a return type"));
+    };
+
+    Ok(parse_quote! {
+        #func
+
+        impl ::advent::FromInput for #output {
+            fn from_input(input: &str) -> ::advent::parse::Result<Self> {
+                let parsed: #parsed_type = ::advent::FromInput::from_input(input)?;
+                Ok(#func_name(parsed))
+            }
+        }
+    })
+}
+
 #[proc_macro_attribute]
-pub fn part_two(_attr: TokenStream, input: TokenStream) -> TokenStream {
-    match part_inner(input, 2) {
+pub fn part_one(attr: TokenStream, input: TokenStream) -> TokenStream {
+    match part_inner(attr, input, 1) {
         Ok(v) => quote!(#v).into(),
         Err(e) => e.into_compile_error().into(),
     }
 }
 
-fn test_for_input(expected: FieldValue) -> Result<ItemFn> {
+#[proc_macro_attribute]
+pub fn part_two(attr: TokenStream, input: TokenStream) -> TokenStream {
+    match part_inner(attr, input, 2) {
+        Ok(v) => quote!(#v).into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+/// Whether an expected answer is an integer literal, which is compared as the answer's own type
+/// rather than as rendered, so it doesn't need a suffix.
+fn is_int_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(_), ..
+        }) => true,
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => is_int_literal(expr),
+        Expr::Paren(ExprParen { expr, .. }) | Expr::Group(ExprGroup { expr, .. }) => {
+            is_int_literal(expr)
+        }
+        _ => false,
+    }
+}
+
+fn test_for_input(
+    expected: FieldValue,
+    file: &str,
+    prefix: Option<&str>,
+    params: Option<&Expr>,
+) -> Result<ItemFn> {
+    // Checked first, so a misspelt part is reported as such rather than as an unknown function.
+    part_number(&expected)?;
     let expected_expr = expected.expr;
-    if let Member::Named(test_name) = expected.member {
-        let sut = Ident::new(&format!("_test_{test_name}"), Span::call_site());
+    if let Member::Named(part) = expected.member {
+        let sut = Ident::new(&format!("_test_{part}"), Span::call_site());
+        let test_name = match prefix {
+            Some(prefix) => Ident::new(&format!("{prefix}_{part}"), part.span()),
+            None => part,
+        };
+        let params: Expr = match params {
+            Some(params) => parse_quote!(Some(::std::boxed::Box::new(#params))),
+            None => parse_quote!(None),
+        };
+        let assertion = if is_int_literal(&expected_expr) {
+            quote!(assert_eq!(#sut(&input_str, #params).unwrap(), #expected_expr);)
+        } else {
+            quote!(::advent::assert_answer(&#sut(&input_str, #params).unwrap(), &#expected_expr);)
+        };
         Ok(parse_quote! {
             #[test]
             fn #test_name() {
                 use ::std::io::Read as _;
                 let root_dir = ::std::env!("CARGO_MANIFEST_DIR");
-                let input_path = ::std::path::Path::new(root_dir).join("input.txt");
+                let input_path = ::std::path::Path::new(root_dir).join(#file);
                 let mut input = ::std::fs::File::open(input_path).unwrap();
                 let mut input_str = ::std::string::String::new();
                 input.read_to_string(&mut input_str).unwrap();
-                assert_eq!(#sut(&input_str).unwrap(), #expected_expr);
+                #assertion
             }
         })
     } else {
@@ -91,42 +376,209 @@ fn test_for_input(expected: FieldValue) -> Result<ItemFn> {
     }
 }
 
-fn main_func() -> ItemFn {
+/// A test that every answer recorded in the day's `answers.toml` still comes out the same. The
+/// file is read when the test runs, so answers recorded since the last build are tested too.
+/// `params` are the harness's params for each input file.
+fn recorded_answers_test(params: Vec<(String, Expr)>) -> ItemFn {
+    let (files, params): (Vec<_>, Vec<_>) = params.into_iter().unzip();
     parse_quote! {
-        fn main() -> ::advent::parse::Result<()> {
-            use ::std::io::Read as _;
-            let mut input = ::std::string::String::new();
-            ::std::io::stdin().lock().read_to_string(&mut input)?;
-            let json = ::std::env::args().any(|a| a == "--json");
+        #[test]
+        fn recorded_answers() {
+            let root_dir = ::std::path::Path::new(::std::env!("CARGO_MANIFEST_DIR"));
+            let answers = ::advent::answers::Answers::load(root_dir).unwrap();
+            for (file, part, expected) in answers.iter() {
+                let input = ::std::fs::read_to_string(root_dir.join(file)).unwrap();
+                let params: ::std::option::Option<::std::boxed::Box<dyn ::std::any::Any>> =
+                    match file {
+                        #(#files => Some(::std::boxed::Box::new(#params)),)*
+                        _ => None,
+                    };
+                let answer = match part {
+                    1 => ::advent::Answer::render(&_test_part_1(&input, params).unwrap()),
+                    _ => ::advent::Answer::render(&_test_part_2(&input, params).unwrap()),
+                };
+                assert!(
+                    ::advent::answer::same(&answer, expected),
+                    "part {part} of {file} is\n{answer}\nnot\n{expected}",
+                );
+            }
+        }
+    }
+}
 
-            _run_part_1(&input, json)?;
-            _run_part_2(&input, json)?;
+/// The day's entry in the registry, `pub static DAY`, which its `main` and the runner run.
+fn day_static() -> Vec<Item> {
+    vec![
+        parse_quote! {
+            /// This day's puzzle, to run it through [`::advent::Solution`].
+            pub struct Puzzle;
+        },
+        parse_quote! {
+            impl ::advent::Solution for Puzzle {
+                type Input = _Part1Input;
+                type Params = <_Part1Params as ::advent::JoinParams<_Part2Params>>::Output;
+                type Answer1 = _Part1Output;
+                type Answer2 = _Part2Output;
 
-            Ok(())
-        }
+                fn parse(input: &str) -> ::advent::parse::Result<Self::Input> {
+                    ::advent::FromInput::from_input_with(&_normalise(), input)
+                }
+
+                fn part_one(input: &Self::Input, params: Self::Params) -> Self::Answer1 {
+                    _part_1(input, params)
+                }
+
+                fn part_two(input: &Self::Input, params: Self::Params) -> Self::Answer2 {
+                    _part_2(input, params)
+                }
+            }
+        },
+        parse_quote! {
+            fn _parse_input(
+                input: &str,
+            ) -> ::advent::parse::Result<::std::boxed::Box<dyn ::std::any::Any>> {
+                // Both parts are given the same parsed input.
+                let _: fn(_Part1Input) -> _Part2Input = |input| input;
+                let input: _Part1Input = ::advent::FromInput::from_input_with(&_normalise(), input)?;
+                Ok(::std::boxed::Box::new(input))
+            }
+        },
+        parse_quote! {
+            /// The entry point for fuzzers, built with `--cfg fuzzing`. Inputs are only limited
+            /// in time and memory with `advent::mem::Counting` as the fuzzer's global allocator.
+            #[allow(unexpected_cfgs)]
+            pub mod fuzz {
+                #[cfg(fuzzing)]
+                pub fn run(data: &[u8]) {
+                    ::advent::fuzz::run_input(&super::DAY, data);
+                }
+            }
+        },
+        parse_quote! {
+            pub static DAY: ::advent::Day = ::advent::Day {
+                name: ::std::env!("CARGO_PKG_NAME"),
+                manifest_dir: ::std::env!("CARGO_MANIFEST_DIR"),
+                parse: _parse_input,
+                parts: [_run_part_1, _run_part_2],
+                references: [_REFERENCE_PART_1, _REFERENCE_PART_2],
+            };
+        },
+    ]
+}
+
+/// The part number from an expected answer like `part_1: 42`.
+fn part_number(f: &FieldValue) -> Result<usize> {
+    match &f.member {
+        Member::Named(m) => match &m.to_string()[..] {
+            "part_1" => Ok(1),
+            "part_2" => Ok(2),
+            _ => Err(Error::new(m.span(), "expected part_1 or part_2")),
+        },
+        m => Err(Error::new(m.span(), "invalid argument")),
     }
 }
 
-struct HarnessInput {
-    input: Punctuated<FieldValue, Comma>,
+struct Example {
+    file: LitStr,
+    _arrow: Token![=>],
+    _parens: Paren,
+    expected: Punctuated<FieldValue, Comma>,
 }
 
-impl Parse for HarnessInput {
+impl Parse for Example {
     fn parse(input: ParseStream) -> Result<Self> {
+        let content;
         Ok(Self {
-            input: Punctuated::parse_terminated(input)?,
+            file: input.parse()?,
+            _arrow: input.parse()?,
+            _parens: parenthesized!(content in input),
+            expected: Punctuated::parse_terminated(&content)?,
         })
     }
 }
 
-fn harness_inner(input: HarnessInput) -> Result<Vec<ItemFn>> {
-    let mut funcs: Vec<_> = input
-        .input
+struct HarnessInput {
+    expected: Vec<FieldValue>,
+    examples: Vec<Example>,
+}
+
+impl Parse for HarnessInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut expected = vec![];
+        let mut examples = vec![];
+        while !input.is_empty() {
+            if input.peek(Ident)
+                && input.peek2(Colon)
+                && input.fork().parse::<Ident>()? == "examples"
+            {
+                input.parse::<Ident>()?;
+                input.parse::<Colon>()?;
+                let content;
+                braced!(content in input);
+                examples.extend(Punctuated::<Example, Comma>::parse_terminated(&content)?);
+            } else {
+                expected.push(input.parse()?);
+            }
+            if !input.is_empty() {
+                input.parse::<Comma>()?;
+            }
+        }
+        Ok(Self { expected, examples })
+    }
+}
+
+/// Splits the `params: expr` entry out of a list of expected answers.
+fn split_params(fields: impl IntoIterator<Item = FieldValue>) -> (Vec<FieldValue>, Option<Expr>) {
+    split_field(fields, "params")
+}
+
+/// Splits the `name: expr` entry out of a list of fields.
+fn split_field(
+    fields: impl IntoIterator<Item = FieldValue>,
+    name: &str,
+) -> (Vec<FieldValue>, Option<Expr>) {
+    let (named, rest): (Vec<_>, Vec<_>) = fields
         .into_iter()
-        .map(test_for_input)
-        .collect::<Result<_>>()?;
-    funcs.push(main_func());
-    Ok(funcs)
+        .partition(|f| matches!(&f.member, Member::Named(m) if m == name));
+    (rest, named.into_iter().next().map(|f| f.expr))
+}
+
+fn harness_inner(input: HarnessInput) -> Result<Vec<Item>> {
+    let mut params_by_file = vec![];
+    // How the input and examples are cleaned up before parsing, `normalise: Normalise { .. }`.
+    let (expected, normalise) = split_field(input.expected, "normalise");
+    let normalise = normalise.unwrap_or(parse_quote!(::std::default::Default::default()));
+    let (expected, params) = split_params(expected);
+    params_by_file.extend(params.clone().map(|p| ("input.txt".to_owned(), p)));
+    let mut funcs = vec![];
+    for e in expected {
+        funcs.push(test_for_input(e, "input.txt", None, params.as_ref())?);
+    }
+    for example in input.examples {
+        let file = example.file.value();
+        let stem = file.split('.').next().unwrap_or_default();
+        let prefix: String = (stem
+            .starts_with(|c: char| c.is_ascii_digit())
+            .then_some('_'))
+        .into_iter()
+        .chain(stem.chars())
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+        let (expected, params) = split_params(example.expected);
+        params_by_file.extend(params.clone().map(|p| (file.clone(), p)));
+        for e in expected {
+            funcs.push(test_for_input(e, &file, Some(&prefix), params.as_ref())?);
+        }
+    }
+    funcs.push(recorded_answers_test(params_by_file));
+    funcs.push(parse_quote! {
+        fn _normalise() -> ::advent::parse::Normalise {
+            #normalise
+        }
+    });
+    let mut items: Vec<Item> = funcs.into_iter().map(Item::Fn).collect();
+    items.extend(day_static());
+    Ok(items)
 }
 
 #[proc_macro]
@@ -138,3 +590,47 @@ pub fn harness(input: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+fn derive_params_inner(input: DeriveInput) -> Result<ItemImpl> {
+    let Data::Struct(DataStruct {
+        fields: Fields::Named(fields),
+        ..
+    }) = input.data
+    else {
+        return Err(Error::new(
+            input.ident.span(),
+            "Params can only be derived for structs with named fields",
+        ));
+    };
+
+    let name = input.ident;
+    let field_names: Vec<_> = fields
+        .named
+        .iter()
+        .map(|f| f.ident.clone().unwrap())
+        .collect();
+    let keys: Vec<_> = field_names.iter().map(|f| f.to_string()).collect();
+    Ok(parse_quote! {
+        impl ::advent::Params for #name {
+            fn set(&mut self, key: &str, value: &str) -> ::std::result::Result<(), String> {
+                match key {
+                    #(#keys => {
+                        self.#field_names = ::advent::parse::parse_str(value)
+                            .map_err(|e| format!("invalid value for `{key}`: {e}"))?;
+                    })*
+                    _ => return Err(format!("unknown parameter `{key}`")),
+                }
+                Ok(())
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(Params)]
+pub fn derive_params(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match derive_params_inner(input) {
+        Ok(v) => quote!(#v).into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
@@ -0,0 +1,72 @@
+//! Generates the runner's list of days from the workspace's members, so a new day only has to be
+//! a member and one of the runner's dependencies.
+
+use std::path::Path;
+use std::{env, fs};
+
+#[path = "src/workspace.rs"]
+mod workspace;
+
+/// The day a member is for: `day` under `[package.metadata.advent]` in its `Cargo.toml`, or else
+/// the day its name spells out. Other members aren't days.
+fn day_of(name: &str, cargo_toml: &str) -> Option<usize> {
+    let metadata = cargo_toml
+        .lines()
+        .skip_while(|l| l.trim() != "[package.metadata.advent]")
+        .skip(1)
+        .take_while(|l| !l.trim_start().starts_with('['))
+        .find_map(|l| {
+            l.trim()
+                .strip_prefix("day")?
+                .trim()
+                .strip_prefix('=')?
+                .trim()
+                .parse()
+                .ok()
+        });
+    metadata.or_else(|| workspace::day_from_name(name))
+}
+
+fn main() {
+    let runner = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let root = runner.parent().unwrap();
+    let read = |path: &Path| {
+        println!("cargo:rerun-if-changed={}", path.display());
+        fs::read_to_string(path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+    };
+    let workspace = read(&root.join("Cargo.toml"));
+    let dependencies = read(&runner.join("Cargo.toml"));
+
+    let lines: Vec<&str> = workspace.lines().collect();
+    let (start, end) = workspace::members(&lines).unwrap();
+    let mut days = vec![];
+    for name in lines[start + 1..end].iter().map(|l| workspace::member(l)) {
+        let cargo_toml = read(&root.join(&name).join("Cargo.toml"));
+        if let Some(day) = day_of(&name, &cargo_toml) {
+            days.push((day, name));
+        }
+    }
+    days.sort();
+
+    let mut table = String::from("pub static DAYS: &[&Day] = &[\n");
+    for (i, (day, name)) in days.iter().enumerate() {
+        // Days are found by position.
+        assert_eq!(
+            *day,
+            i + 1,
+            "no day {} in the workspace, before {name}",
+            i + 1
+        );
+        assert!(
+            dependencies
+                .lines()
+                .any(|l| l.split_once('=').is_some_and(|(dep, _)| dep.trim() == name)),
+            "{name} isn't one of the runner's dependencies; add `{name} = {{ path = \"../{name}\" }}` \
+             to advent_runner/Cargo.toml"
+        );
+        table += &format!("    &{name}::DAY,\n");
+    }
+    table += "];\n";
+    let out = Path::new(&env::var_os("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out, table).unwrap();
+}
@@ -0,0 +1,131 @@
+use std::path::Path;
+use std::time::Duration;
+use std::{fmt, fs, io};
+
+/// Median times recorded for each day on this machine, kept in `baselines.toml` as nanoseconds
+/// along with the build profile they were recorded with:
+///
+/// ```toml
+/// profile = "release"
+///
+/// [one]
+/// parse = 48213
+/// part_1 = 1520
+/// part_2 = 2114
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Baselines {
+    /// `release` or `debug`, as times from one aren't comparable with the other.
+    profile: Option<String>,
+    days: Vec<(String, [Option<Duration>; 3])>,
+}
+
+/// The phases timed, in the order they're kept.
+pub const PHASES: [&str; 3] = ["parse", "part_1", "part_2"];
+
+pub const FILE: &str = "baselines.toml";
+
+impl Baselines {
+    /// No baselines yet, to be recorded with `profile`.
+    pub fn new(profile: &str) -> Self {
+        Self {
+            profile: Some(profile.to_owned()),
+            days: vec![],
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut baselines = Self::default();
+        for (i, line) in s.lines().enumerate() {
+            let err = |e: &str| format!("{FILE}:{}: {e}", i + 1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(day) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                baselines.days.push((day.trim().to_owned(), [None; 3]));
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or(err("expected `key = value`"))?;
+            if key.trim() == "profile" && baselines.days.is_empty() {
+                let profile = value
+                    .trim()
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'));
+                baselines.profile = Some(profile.ok_or(err("expected a quoted profile"))?.into());
+                continue;
+            }
+            let phase = PHASES
+                .iter()
+                .position(|p| *p == key.trim())
+                .ok_or(err(&format!("unknown key `{}`", key.trim())))?;
+            let nanos = value
+                .trim()
+                .parse()
+                .map_err(|_| err("expected nanoseconds"))?;
+            let Some((_, phases)) = baselines.days.last_mut() else {
+                return Err(err("time outside of a day's table"));
+            };
+            phases[phase] = Some(Duration::from_nanos(nanos));
+        }
+        Ok(baselines)
+    }
+
+    /// Reads the baselines in `dir`, if there are any.
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        match fs::read_to_string(dir.join(FILE)) {
+            Ok(s) => Self::parse(&s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, dir: &Path) -> io::Result<()> {
+        fs::write(dir.join(FILE), self.to_string())
+    }
+
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+
+    /// The baseline for a phase of a day, as an index into [`PHASES`].
+    pub fn get(&self, day: &str, phase: usize) -> Option<Duration> {
+        let (_, phases) = self.days.iter().find(|(d, _)| d == day)?;
+        phases[phase]
+    }
+
+    pub fn set(&mut self, day: &str, phase: usize, time: Duration) {
+        let index = match self.days.iter().position(|(d, _)| d == day) {
+            Some(index) => index,
+            None => {
+                self.days.push((day.to_owned(), [None; 3]));
+                self.days.len() - 1
+            }
+        };
+        self.days[index].1[phase] = Some(time);
+    }
+}
+
+impl fmt::Display for Baselines {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(profile) = &self.profile {
+            writeln!(f, "profile = \"{profile}\"")?;
+        }
+        for (i, (day, phases)) in self.days.iter().enumerate() {
+            if i > 0 || self.profile.is_some() {
+                writeln!(f)?;
+            }
+            writeln!(f, "[{day}]")?;
+            for (phase, time) in PHASES.iter().zip(phases) {
+                if let Some(time) = time {
+                    writeln!(f, "{phase} = {}", time.as_nanos())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
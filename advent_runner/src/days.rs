@@ -0,0 +1,5 @@
+use advent::Day;
+
+// `DAYS`, every day in the workspace in order, so day `n` is at index `n - 1`. Generated by the
+// build script.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
@@ -0,0 +1,538 @@
+use advent::bench::Stats;
+use advent::fuzz::{self, FuzzOptions};
+use advent::{parse, Day, RunOptions};
+use baseline::{Baselines, PHASES};
+use days::DAYS;
+use std::iter::Peekable;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use std::{env, fs, thread};
+
+#[global_allocator]
+static ALLOCATOR: advent::mem::Counting = advent::mem::Counting;
+
+mod baseline;
+mod days;
+mod new;
+mod workspace;
+
+const USAGE: &str = "\
+usage: advent-runner [all | DAY | FIRST..LAST]... [options]
+       advent-runner fuzz DAY... [fuzz options]
+       advent-runner new NAME [new options]
+
+Days are given by number or name, like 7 or seven, and default to all of them. Baselines are
+only comparable on the same machine, and are only compared with timings from the same build
+profile, release or debug, as they were recorded with.
+
+options:
+  --jobs [N]            run N days at once (default: one per core)
+  --bench [RUNS]        time each phase over RUNS runs (default 20), showing the medians
+  --save-baseline       record the timings in baselines.toml, implying --bench
+  --compare [FACTOR]    fail if a phase got slower than in baselines.toml by more than FACTOR
+                        (default 1.5), implying --bench
+  --help                show this message
+
+fuzz options:
+  --runs N        stop after N inputs
+  --time SECS     stop after SECS seconds, when not counting runs (default 10)
+  --budget SECS   how long an input may run before it counts as a hang (default 1)
+  --memory MIB    how much an input may have allocated at once (default 1024)
+  --seed N        seed the mutations
+
+new options, for creating a day's crate from the templates in advent_runner/templates:
+  --day N         the day, when NAME isn't its number in words like seven or twenty_one
+  --year Y        the year (default 2022)
+  --input PATH    copy the input from PATH, rather than from $ADVENT_CACHE/Y/N.txt
+                  (default cache: ~/.cache/advent)";
+
+/// The build profile timings are recorded with, since debug and release builds aren't comparable.
+const PROFILE: &str = if cfg!(debug_assertions) {
+    "debug"
+} else {
+    "release"
+};
+
+/// How many more times a day that looks slower than its baseline is measured.
+const RETRIES: usize = 2;
+
+/// Slowdowns smaller than this are put down to noise, however large the factor.
+const NOISE_FLOOR: Duration = Duration::from_micros(20);
+
+struct Options {
+    days: Vec<usize>,
+    jobs: usize,
+    /// Number of timed runs of each phase, when benchmarking.
+    bench: Option<usize>,
+    save_baseline: bool,
+    /// The factor a phase may slow down by before failing, when comparing with baselines.
+    compare: Option<f64>,
+}
+
+struct PartRow {
+    answer: Result<String, String>,
+    matches: Option<bool>,
+    solve_time: Duration,
+    bench: Option<Stats>,
+}
+
+/// What running a day on its `input.txt` produced.
+struct DayRun {
+    parse_time: Duration,
+    parse_bench: Option<Stats>,
+    parts: Vec<PartRow>,
+}
+
+impl DayRun {
+    /// The timings of each phase, in the order of [`PHASES`].
+    fn bench(&self) -> Vec<Option<Stats>> {
+        let parts = self.parts.iter().map(|p| p.bench);
+        [self.parse_bench].into_iter().chain(parts).collect()
+    }
+
+    /// Keeps whichever of this run's and `other`'s timings of each phase were fastest.
+    fn merge_fastest(&mut self, other: DayRun) {
+        let faster = |a: &Option<Stats>, b: &Option<Stats>| match (a, b) {
+            (Some(a), Some(b)) => b.min < a.min,
+            _ => false,
+        };
+        if faster(&self.parse_bench, &other.parse_bench) {
+            self.parse_time = other.parse_time;
+            self.parse_bench = other.parse_bench;
+        }
+        for (part, other) in self.parts.iter_mut().zip(other.parts) {
+            if faster(&part.bench, &other.bench) {
+                part.solve_time = other.solve_time;
+                part.bench = other.bench;
+            }
+        }
+    }
+}
+
+struct Row {
+    day: usize,
+    result: Result<DayRun, String>,
+    /// Phases slower than their baselines, as the index into [`PHASES`], the baseline and the
+    /// factor.
+    slower: Vec<(usize, Duration, f64)>,
+}
+
+fn run(day: &Day, bench: Option<usize>) -> parse::Result<DayRun> {
+    let path = Path::new(day.manifest_dir).join("input.txt");
+    let input = fs::read_to_string(&path)
+        .map_err(|e| parse::Error::ParseError(format!("{}: {e}", path.display())))?;
+    let options = RunOptions {
+        input: Some(path),
+        bench,
+        ..RunOptions::default()
+    };
+    let parsed = day.parse_input(&input, &options)?;
+    let mut parts = vec![];
+    for run in &day.parts {
+        let report = run(&*parsed.value, &options)?;
+        parts.push(PartRow {
+            matches: day.check(options.input.as_deref(), &report),
+            answer: report.answer,
+            solve_time: report.solve_time,
+            bench: report.bench,
+        });
+    }
+    Ok(DayRun {
+        parse_time: parsed.time,
+        parse_bench: parsed.stats,
+        parts,
+    })
+}
+
+/// Runs a day, turning a panic into an error so the other days still run.
+fn run_caught(day: &Day, bench: Option<usize>) -> Result<DayRun, String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| run(day, bench))) {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(payload) => {
+            let message = match (
+                payload.downcast_ref::<&str>(),
+                payload.downcast_ref::<String>(),
+            ) {
+                (Some(message), _) => message,
+                (_, Some(message)) => message.as_str(),
+                _ => "",
+            };
+            Err(format!("panicked: {message}"))
+        }
+    }
+}
+
+/// The phases of a day that got slower than their baselines by more than `factor`. A phase's
+/// fastest run is compared with the baseline's median, so noise alone rarely makes one slower.
+fn slower_phases(
+    name: &str,
+    run: &DayRun,
+    baselines: &Baselines,
+    factor: f64,
+) -> Vec<(usize, Duration, f64)> {
+    let mut slower = vec![];
+    for (phase, stats) in run.bench().into_iter().enumerate() {
+        let (Some(stats), Some(baseline)) = (stats, baselines.get(name, phase)) else {
+            continue;
+        };
+        let ratio = stats.min.as_secs_f64() / baseline.as_secs_f64().max(1e-9);
+        if ratio > factor && stats.min.saturating_sub(baseline) > NOISE_FLOOR {
+            slower.push((phase, baseline, ratio));
+        }
+    }
+    slower
+}
+
+/// Compares each day with its baselines, measuring days that look slower again before deciding
+/// they are.
+fn compare(rows: &mut [Row], baselines: &Baselines, factor: f64, runs: usize) {
+    for attempt in 0..=RETRIES {
+        for row in rows.iter_mut() {
+            let day = DAYS[row.day - 1];
+            let Ok(result) = &mut row.result else {
+                continue;
+            };
+            if attempt > 0 {
+                if row.slower.is_empty() {
+                    continue;
+                }
+                if let Ok(again) = run_caught(day, Some(runs)) {
+                    result.merge_fastest(again);
+                }
+            }
+            row.slower = slower_phases(day.name, result, baselines, factor);
+        }
+    }
+}
+
+/// Runs the days on `jobs` threads, returning their rows in order.
+fn run_all(days: &[usize], jobs: usize, bench: Option<usize>) -> Vec<Row> {
+    let next = AtomicUsize::new(0);
+    let rows = Mutex::new(vec![]);
+    thread::scope(|s| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            s.spawn(|| {
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let result = run_caught(DAYS[day - 1], bench);
+                    rows.lock().unwrap().push(Row {
+                        day,
+                        result,
+                        slower: vec![],
+                    });
+                }
+            });
+        }
+    });
+    let mut rows = rows.into_inner().unwrap();
+    rows.sort_by_key(|r| r.day);
+    rows
+}
+
+/// The day numbered or named `s`.
+fn day_number(s: &str) -> Result<usize, String> {
+    let day = match s.parse::<usize>() {
+        Ok(n) => n,
+        Err(_) => DAYS.iter().position(|d| d.name == s).map_or(0, |i| i + 1),
+    };
+    if (1..=DAYS.len()).contains(&day) {
+        Ok(day)
+    } else {
+        Err(format!("no day {s:?}"))
+    }
+}
+
+/// The argument after a flag taking an optional number, if it is one.
+fn number_arg(args: &mut Peekable<impl Iterator<Item = String>>) -> Option<f64> {
+    let n = args.peek().and_then(|a| a.parse().ok());
+    if n.is_some() {
+        args.next();
+    }
+    n
+}
+
+/// What to run and how, from the command line.
+fn from_args(args: impl IntoIterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut days = vec![];
+    let mut jobs = 1;
+    let (mut bench, mut save_baseline, mut compare) = (None, false, None);
+    let mut args = args.into_iter().peekable();
+    while let Some(arg) = args.next() {
+        match &arg[..] {
+            "--help" | "-h" => return Ok(None),
+            "--bench" => bench = Some(number_arg(&mut args).map_or(20, |n| n as usize)),
+            "--save-baseline" => save_baseline = true,
+            "--compare" => match number_arg(&mut args).unwrap_or(1.5) {
+                factor if factor >= 1.0 => compare = Some(factor),
+                _ => return Err("--compare takes a factor of at least 1".into()),
+            },
+            "--jobs" | "-j" => {
+                jobs = match args.peek().and_then(|a| a.parse().ok()) {
+                    Some(n) => {
+                        args.next();
+                        n
+                    }
+                    None => thread::available_parallelism().map_or(1, |n| n.get()),
+                }
+            }
+            "all" => days.extend(1..=DAYS.len()),
+            arg if arg.starts_with('-') => return Err(format!("unknown argument {arg:?}")),
+            arg => match arg.split_once("..") {
+                Some((first, last)) => days.extend(day_number(first)?..=day_number(last)?),
+                None => days.push(day_number(arg)?),
+            },
+        }
+    }
+    if days.is_empty() {
+        days.extend(1..=DAYS.len());
+    }
+    days.sort_unstable();
+    days.dedup();
+    if save_baseline || compare.is_some() {
+        bench = bench.or(Some(20));
+    }
+    Ok(Some(Options {
+        days,
+        jobs,
+        bench,
+        save_baseline,
+        compare,
+    }))
+}
+
+/// An answer as it fits in the table.
+fn cell(part: &PartRow) -> String {
+    let answer = match &part.answer {
+        Ok(answer) if answer.trim().contains('\n') => {
+            format!("({} lines)", answer.trim().lines().count())
+        }
+        Ok(answer) => answer.clone(),
+        Err(_) => "failed".into(),
+    };
+    match part.matches {
+        Some(true) => format!("{answer} ✓"),
+        Some(false) => format!("{answer} ✗"),
+        None => answer,
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    let mut table =
+        vec![["day", "part 1", "part 2", "parse", "solve 1", "solve 2"].map(String::from)];
+    let mut errors = vec![];
+    let mut total = Duration::ZERO;
+    for row in rows {
+        let name = DAYS[row.day - 1].name;
+        let mut line = [
+            format!("{:2} {name}", row.day),
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+        ];
+        match &row.result {
+            Ok(run) => {
+                line[3] = format!("{:.2?}", run.parse_time);
+                total += run.parse_time;
+                for (i, part) in run.parts.iter().enumerate() {
+                    line[1 + i] = cell(part);
+                    line[4 + i] = format!("{:.2?}", part.solve_time);
+                    total += part.solve_time;
+                    if let Err(e) = &part.answer {
+                        errors.push(format!("{name} part {}: {e}", i + 1));
+                    }
+                }
+                for &(phase, baseline, ratio) in &row.slower {
+                    line[3 + phase] += &format!(" ({ratio:.1}x)");
+                    errors.push(format!(
+                        "{name} {} is {ratio:.1}x slower than its baseline of {baseline:.2?}",
+                        PHASES[phase].replace('_', " ")
+                    ));
+                }
+            }
+            Err(e) => {
+                line[1] = "error".into();
+                errors.push(format!("{name}: {e}"));
+            }
+        }
+        table.push(line);
+    }
+
+    let widths: Vec<_> = (0..6)
+        .map(|i| {
+            table
+                .iter()
+                .map(|l| l[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    for line in &table {
+        let cells: Vec<_> = line
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{c}{}", " ".repeat(w - c.chars().count())))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+    println!("\ntotal {total:.2?}");
+    for e in errors {
+        eprintln!("error: {e}");
+    }
+}
+
+/// The days to fuzz and how, from the command line after `fuzz`.
+fn fuzz_args(args: impl IntoIterator<Item = String>) -> Result<(Vec<usize>, FuzzOptions), String> {
+    let mut days = vec![];
+    let mut options = FuzzOptions::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |what: &str| {
+            args.next()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| *v >= 0.0)
+                .ok_or(format!("{arg} takes {what}"))
+        };
+        match &arg[..] {
+            "--runs" => options.runs = Some(value("a number of runs")? as usize),
+            "--time" => options.time = Duration::from_secs_f64(value("a number of seconds")?),
+            "--budget" => options.budget = Duration::from_secs_f64(value("a number of seconds")?),
+            "--memory" => options.memory = (value("a number of MiB")? * (1 << 20) as f64) as usize,
+            "--seed" => options.seed = value("a number")? as u64,
+            arg if arg.starts_with('-') => return Err(format!("unknown argument {arg:?}")),
+            arg => days.push(day_number(arg)?),
+        }
+    }
+    if days.is_empty() {
+        return Err("fuzz needs a day".into());
+    }
+    Ok((days, options))
+}
+
+fn fuzz_main(args: impl IntoIterator<Item = String>) -> ExitCode {
+    let (days, options) = match fuzz_args(args) {
+        Ok(fuzz) => fuzz,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    for day in days {
+        let day = DAYS[day - 1];
+        let report = fuzz::fuzz(day, &options);
+        println!(
+            "{}: {} runs, {} parsed, corpus of {}",
+            day.name, report.runs, report.parsed, report.corpus
+        );
+        if let Some((failure, path)) = report.failure {
+            eprintln!(
+                "error: {} {failure} on the input saved to {}",
+                day.name,
+                path.display()
+            );
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn new_main(args: impl IntoIterator<Item = String>) -> ExitCode {
+    let result = new::from_args(args).map_err(|e| format!("{e}\n\n{USAGE}"));
+    match result.and_then(|new| new::new_day(&new, DAYS.len())) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1).peekable();
+    match args.peek().map(|a| &a[..]) {
+        Some("fuzz") => return fuzz_main(args.skip(1)),
+        Some("new") => return new_main(args.skip(1)),
+        _ => {}
+    }
+    let options = match from_args(args) {
+        Ok(Some(selection)) => selection,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut baselines = match Baselines::load(dir) {
+        Ok(baselines) => baselines,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if options.compare.is_some() && baselines.is_empty() {
+        eprintln!(
+            "error: no baselines in {}; record some on this machine with --save-baseline",
+            dir.join(baseline::FILE).display()
+        );
+        return ExitCode::FAILURE;
+    }
+    if options.compare.is_some() && baselines.profile() != Some(PROFILE) {
+        eprintln!(
+            "error: the baselines in {} were recorded with {} build, not this {PROFILE} one; \
+             compare with that profile or record them again with --save-baseline",
+            dir.join(baseline::FILE).display(),
+            baselines
+                .profile()
+                .map_or("an unknown".to_owned(), |p| format!("a {p}")),
+        );
+        return ExitCode::FAILURE;
+    }
+    let mut rows = run_all(&options.days, options.jobs, options.bench);
+    if let (Some(factor), Some(runs)) = (options.compare, options.bench) {
+        compare(&mut rows, &baselines, factor, runs);
+    }
+    print_table(&rows);
+    let ok = rows.iter().all(|row| match &row.result {
+        Ok(run) => {
+            row.slower.is_empty()
+                && run
+                    .parts
+                    .iter()
+                    .all(|p| p.answer.is_ok() && p.matches != Some(false))
+        }
+        Err(_) => false,
+    });
+
+    if options.save_baseline {
+        if baselines.profile() != Some(PROFILE) {
+            // Another profile's times would be compared as if they were this one's.
+            baselines = Baselines::new(PROFILE);
+        }
+        for row in &rows {
+            let Ok(run) = &row.result else { continue };
+            for (phase, stats) in run.bench().into_iter().enumerate() {
+                if let Some(stats) = stats {
+                    baselines.set(DAYS[row.day - 1].name, phase, stats.median);
+                }
+            }
+        }
+        if let Err(e) = baselines.save(dir) {
+            eprintln!("error: {}: {e}", dir.join(baseline::FILE).display());
+            return ExitCode::FAILURE;
+        }
+        println!("baselines saved to {}", dir.join(baseline::FILE).display());
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,170 @@
+use crate::workspace::{day_from_name, member, members};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+/// The year puzzles are for, unless `--year` says otherwise.
+const DEFAULT_YEAR: u32 = 2022;
+
+/// The files a new day is made from, as the template in `templates/` and where it goes.
+const TEMPLATES: [(&str, &str); 4] = [
+    ("Cargo.toml.template", "Cargo.toml"),
+    ("lib.rs", "src/lib.rs"),
+    ("main.rs", "src/main.rs"),
+    ("example.txt", "example.txt"),
+];
+
+pub struct NewDay {
+    pub name: String,
+    pub day: usize,
+    pub year: u32,
+    /// Where to copy `input.txt` from, rather than the cache.
+    pub input: Option<PathBuf>,
+}
+
+/// The day to create, from the command line after `new`.
+pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<NewDay, String> {
+    let (mut name, mut day, mut year, mut input) = (None, None, DEFAULT_YEAR, None);
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |what: &str| args.next().ok_or(format!("{arg} takes {what}"));
+        match &arg[..] {
+            "--day" => {
+                let n = value("a day")?
+                    .parse()
+                    .ok()
+                    .filter(|n| (1..=25).contains(n));
+                day = Some(n.ok_or("--day takes a day from 1 to 25")?);
+            }
+            "--year" => {
+                year = value("a year")?
+                    .parse()
+                    .map_err(|_| "--year takes a year")?
+            }
+            "--input" => input = Some(PathBuf::from(value("a path")?)),
+            arg if arg.starts_with('-') => return Err(format!("unknown argument {arg:?}")),
+            arg if name.is_none() => name = Some(arg.to_owned()),
+            arg => return Err(format!("unexpected argument {arg:?}")),
+        }
+    }
+    let name = name.ok_or("new needs the new day's name")?;
+    if !name.chars().all(|c| c.is_ascii_lowercase() || c == '_') {
+        return Err(format!("{name:?} isn't a lowercase crate name"));
+    }
+    let day = day
+        .or_else(|| day_from_name(&name))
+        .ok_or(format!("can't tell which day {name:?} is, so give --day"))?;
+    Ok(NewDay {
+        name,
+        day,
+        year,
+        input,
+    })
+}
+
+/// Where inputs are looked for when `--input` isn't given: `$ADVENT_CACHE`, or else
+/// `~/.cache/advent`, holding `<year>/<day>.txt`.
+fn cached_input(year: u32, day: usize) -> Option<PathBuf> {
+    let cache = match env::var_os("ADVENT_CACHE") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(&env::var_os("HOME")?).join(".cache/advent"),
+    };
+    Some(cache.join(format!("{year}/{day}.txt")))
+}
+
+/// Adds `name` to the workspace's members, keeping them sorted and the rest of the file as it is.
+fn add_to_workspace(cargo_toml: &str, name: &str) -> Result<String, String> {
+    let lines: Vec<&str> = cargo_toml.lines().collect();
+    let (start, end) = members(&lines)?;
+    let members = &lines[start + 1..end];
+    if members.iter().any(|l| member(l) == name) {
+        return Err(format!("{name} is already a workspace member"));
+    }
+    // Copied from the first member, so the new one is indented and quoted the same way.
+    let line = match members.first() {
+        Some(first) => first.replacen(&member(first), name, 1),
+        None => format!("    '{name}',"),
+    };
+    let at = start + 1 + members.partition_point(|l| member(l).as_str() < name);
+
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    out.insert(at, line);
+    Ok(out.join("\n") + "\n")
+}
+
+/// Adds the day to the runner's dependencies. Its list of days is generated from the workspace's
+/// members by the build script.
+fn add_to_runner(runner: &Path, new: &NewDay) -> Result<(), String> {
+    let cargo_toml = runner.join("Cargo.toml");
+    let mut manifest = fs::read_to_string(&cargo_toml).map_err(|e| e.to_string())?;
+    if !manifest.ends_with('\n') {
+        manifest.push('\n');
+    }
+    manifest += &format!("{0} = {{ path = \"../{0}\" }}\n", new.name);
+    fs::write(&cargo_toml, manifest).map_err(|e| e.to_string())
+}
+
+/// Creates the day's crate from the templates and registers it with the workspace and the
+/// runner, which has `days` days so far.
+pub fn new_day(new: &NewDay, days: usize) -> Result<(), String> {
+    // Days are found by position in the runner's list.
+    if new.day != days + 1 {
+        return Err(format!(
+            "the runner has {days} days, so the next is day {}, not {}",
+            days + 1,
+            new.day
+        ));
+    }
+    let runner = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let root = runner.parent().unwrap();
+    let dir = root.join(&new.name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let workspace_toml = root.join("Cargo.toml");
+    let workspace = fs::read_to_string(&workspace_toml).map_err(|e| e.to_string())?;
+    let workspace = add_to_workspace(&workspace, &new.name)?;
+
+    let input = new
+        .input
+        .clone()
+        .or_else(|| cached_input(new.year, new.day));
+    let input = match input.as_deref().map(fs::read_to_string) {
+        Some(Ok(input)) => Some(input),
+        Some(Err(e)) if new.input.is_some() => {
+            return Err(format!("{}: {e}", input.unwrap().display()))
+        }
+        _ => None,
+    };
+
+    let fill = |template: &str| {
+        template
+            .replace("<name>", &new.name)
+            .replace("<day>", &new.day.to_string())
+            .replace("<year>", &new.year.to_string())
+    };
+    let write = |path: &Path, contents: &str| {
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|()| fs::write(path, contents))
+            .map_err(|e| format!("{}: {e}", path.display()))
+    };
+    for (template, file) in TEMPLATES {
+        let template = runner.join("templates").join(template);
+        let template =
+            fs::read_to_string(&template).map_err(|e| format!("{}: {e}", template.display()))?;
+        write(&dir.join(file), &fill(&template))?;
+    }
+    write(&dir.join("input.txt"), input.as_deref().unwrap_or_default())?;
+    write(&workspace_toml, &workspace)?;
+    add_to_runner(runner, new)?;
+
+    println!("created {} for day {}", dir.display(), new.day);
+    if input.is_none() {
+        let cached = cached_input(new.year, new.day).unwrap_or_default();
+        println!(
+            "no input found at {}, so {}/input.txt is empty",
+            cached.display(),
+            new.name
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Reading the workspace's `Cargo.toml`, shared by `new` and the build script that lists the days.
+
+const NUMBERS: [&str; 19] = [
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+/// The day a crate named like `seven` or `twenty_one` is for.
+pub fn day_from_name(name: &str) -> Option<usize> {
+    let number = |word: &str| NUMBERS.iter().position(|n| *n == word).map(|i| i + 1);
+    match name.split_once(['_', '-']) {
+        Some(("twenty", rest)) => number(rest).filter(|n| *n < 10).map(|n| 20 + n),
+        Some(_) => None,
+        None if name == "twenty" => Some(20),
+        None => number(name),
+    }
+}
+
+/// The lines of `members = [` and of its closing `]`, for members listed one per line.
+pub fn members(lines: &[&str]) -> Result<(usize, usize), String> {
+    let start = lines
+        .iter()
+        .position(|l| l.trim_start().starts_with("members = ["))
+        .ok_or("no workspace members in Cargo.toml")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| l.trim() == "]")
+            .ok_or("expected the workspace members one per line")?;
+    Ok((start, end))
+}
+
+/// The crate named on one of the members' lines.
+pub fn member(line: &str) -> String {
+    line.trim()
+        .trim_end_matches(',')
+        .trim_matches(['\'', '"'])
+        .to_owned()
+}
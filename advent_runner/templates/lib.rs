@@ -0,0 +1,21 @@
+#![feature(type_alias_impl_trait, impl_trait_in_assoc_type)]
+
+//! Advent of Code <year> day <day>: https://adventofcode.com/<year>/day/<day>
+
+use advent::prelude::*;
+
+#[part_one]
+fn part_one(_: String) -> &'static str {
+    "incomplete"
+}
+
+#[part_two]
+fn part_two(_: String) -> &'static str {
+    "incomplete"
+}
+
+harness!(
+    examples: {
+        "example.txt" => (),
+    },
+);
@@ -0,0 +1,6 @@
+#[global_allocator]
+static ALLOCATOR: advent::mem::Counting = advent::mem::Counting;
+
+fn main() -> std::process::ExitCode {
+    advent::run_day(&<name>::DAY)
+}
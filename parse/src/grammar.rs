@@ -0,0 +1,326 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+pub trait HasGrammar {
+    fn grammar() -> Grammar;
+}
+
+#[derive(Clone, Debug)]
+pub enum Grammar {
+    Literal(String),
+    Terminal(&'static str),
+    Field(&'static str, Box<Grammar>),
+    Sequence(Vec<Grammar>),
+    Choice(Vec<Grammar>),
+    Repeat {
+        item: Box<Grammar>,
+        sep: Option<Box<Grammar>>,
+        min: usize,
+        max: usize,
+    },
+    Rule(&'static str, fn() -> Grammar),
+}
+
+impl Grammar {
+    pub fn literal(s: impl Into<String>) -> Self {
+        Self::Literal(s.into())
+    }
+
+    pub fn field(name: &'static str, g: Self) -> Self {
+        Self::Field(name, Box::new(g))
+    }
+
+    pub fn repeat(item: Self, sep: Option<Self>) -> Self {
+        Self::repeat_bounded(item, sep, 1, usize::MAX)
+    }
+
+    pub fn repeat_bounded(item: Self, sep: Option<Self>, min: usize, max: usize) -> Self {
+        Self::Repeat {
+            item: Box::new(item),
+            sep: sep.map(Box::new),
+            min,
+            max,
+        }
+    }
+
+    pub fn rule(name: &'static str, body: fn() -> Grammar) -> Self {
+        Self::Rule(name, body)
+    }
+
+    fn for_each_child(&self, mut f: impl FnMut(&Self)) {
+        match self {
+            Self::Literal(_) | Self::Terminal(_) | Self::Rule(..) => {}
+            Self::Field(_, g) => f(g),
+            Self::Sequence(gs) | Self::Choice(gs) => gs.iter().for_each(f),
+            Self::Repeat { item, sep, .. } => {
+                f(item);
+                if let Some(sep) = sep {
+                    f(sep);
+                }
+            }
+        }
+    }
+
+    /// Every production reachable from this grammar, starting with this one. Rules are expanded
+    /// lazily so recursive types only appear once.
+    fn productions(&self) -> Vec<(&'static str, Grammar)> {
+        fn visit(
+            g: &Grammar,
+            seen: &mut BTreeSet<&'static str>,
+            out: &mut Vec<(&'static str, Grammar)>,
+        ) {
+            if let Grammar::Rule(name, body) = g {
+                if seen.insert(name) {
+                    let body = body();
+                    out.push((name, body.clone()));
+                    visit(&body, seen, out);
+                }
+            } else {
+                g.for_each_child(|c| visit(c, seen, out));
+            }
+        }
+
+        let mut seen = BTreeSet::new();
+        let mut out = vec![];
+        if !matches!(self, Self::Rule(..)) {
+            out.push(("input", self.clone()));
+        }
+        visit(self, &mut seen, &mut out);
+        out
+    }
+
+    pub fn ebnf(&self) -> String {
+        self.productions()
+            .into_iter()
+            .map(|(name, body)| format!("{name} = {} ;\n", Ebnf(&body, 0)))
+            .collect()
+    }
+
+    pub fn railroad(&self) -> String {
+        let mut out = String::new();
+        for (name, body) in self.productions() {
+            out += &format!("{name}:\n");
+            let d = Diagram::new(&body);
+            for (i, line) in d.lines.iter().enumerate() {
+                let line = if i == 0 {
+                    format!(">>-{line}-><")
+                } else {
+                    format!("   {line}")
+                };
+                out += line.trim_end();
+                out += "\n";
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for Grammar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ebnf())
+    }
+}
+
+struct Ebnf<'a>(&'a Grammar, u8);
+
+impl fmt::Display for Ebnf<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(g, prec) = *self;
+        match g {
+            Grammar::Literal(s) => write!(f, "{s:?}"),
+            Grammar::Terminal(t) => write!(f, "? {t} ?"),
+            Grammar::Rule(name, _) => write!(f, "{name}"),
+            Grammar::Field(_, g) => write!(f, "{}", Ebnf(g, prec)),
+            Grammar::Sequence(gs) if gs.is_empty() => write!(f, "\"\""),
+            Grammar::Sequence(gs) => {
+                let parts: Vec<_> = gs.iter().map(|g| Ebnf(g, 2).to_string()).collect();
+                if prec > 1 && gs.len() > 1 {
+                    write!(f, "( {} )", parts.join(", "))
+                } else {
+                    write!(f, "{}", parts.join(", "))
+                }
+            }
+            Grammar::Choice(gs) => {
+                let parts: Vec<_> = gs.iter().map(|g| Ebnf(g, 1).to_string()).collect();
+                if prec > 0 {
+                    write!(f, "( {} )", parts.join(" | "))
+                } else {
+                    write!(f, "{}", parts.join(" | "))
+                }
+            }
+            Grammar::Repeat {
+                item,
+                sep,
+                min,
+                max,
+            } => {
+                let item = Ebnf(item, 1);
+                let mut body = match sep {
+                    Some(sep) => format!("{item}, {{ {}, {item} }}", Ebnf(sep, 1)),
+                    None => format!("{item}, {{ {item} }}"),
+                };
+                if let Some(bounds) = describe_bounds(*min, *max) {
+                    body += &format!(" (* {bounds} items *)");
+                }
+                if *min == 0 {
+                    write!(f, "[ {body} ]")
+                } else if prec > 1 {
+                    write!(f, "( {body} )")
+                } else {
+                    write!(f, "{body}")
+                }
+            }
+        }
+    }
+}
+
+/// How many items a repeat allows, when that's something other than "one or more" or "any".
+pub(crate) fn describe_bounds(min: usize, max: usize) -> Option<String> {
+    match (min, max) {
+        (0 | 1, usize::MAX) => None,
+        (min, usize::MAX) => Some(format!("at least {min}")),
+        (min, max) if min == max => Some(format!("{min}")),
+        (min, max) => Some(format!("{min} to {max}")),
+    }
+}
+
+/// A block of text for the railroad rendering. The rail enters and leaves on the first line.
+struct Diagram {
+    lines: Vec<String>,
+    width: usize,
+}
+
+fn pad(s: &str, width: usize, fill: char) -> String {
+    let mut s = s.to_owned();
+    s.extend(std::iter::repeat_n(fill, width - s.chars().count()));
+    s
+}
+
+impl Diagram {
+    fn atom(s: String) -> Self {
+        Self {
+            width: s.chars().count(),
+            lines: vec![s],
+        }
+    }
+
+    fn new(g: &Grammar) -> Self {
+        match g {
+            Grammar::Literal(s) => Self::atom(format!("{s:?}")),
+            Grammar::Terminal(t) => Self::atom(format!("<{t}>")),
+            Grammar::Rule(name, _) => Self::atom(format!("[{name}]")),
+            Grammar::Field(name, g) => Self::labeled(name, Self::new(g)),
+            Grammar::Sequence(gs) => Self::sequence(gs.iter().map(Self::new).collect()),
+            Grammar::Choice(gs) => Self::choice(gs.iter().map(Self::new).collect()),
+            Grammar::Repeat {
+                item,
+                sep,
+                min,
+                max,
+            } => {
+                let d = Self::repeat(
+                    Self::new(item),
+                    sep.as_ref().map(|s| Self::new(s)),
+                    describe_bounds(*min, *max),
+                );
+                if *min == 0 {
+                    Self::choice(vec![Self::atom(String::new()), d])
+                } else {
+                    d
+                }
+            }
+        }
+    }
+
+    fn labeled(name: &str, d: Self) -> Self {
+        let label = format!("{name}:");
+        let indent = " ".repeat(label.len());
+        let lines = d
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(i, l)| {
+                if i == 0 {
+                    format!("{label}{l}")
+                } else {
+                    format!("{indent}{l}")
+                }
+            })
+            .collect();
+        Self {
+            lines,
+            width: d.width + label.len(),
+        }
+    }
+
+    fn sequence(ds: Vec<Self>) -> Self {
+        let mut ds = ds.into_iter();
+        let Some(mut acc) = ds.next() else {
+            return Self::atom(String::new());
+        };
+        for d in ds {
+            let height = std::cmp::max(acc.lines.len(), d.lines.len());
+            let lines = (0..height)
+                .map(|i| {
+                    let left = acc.lines.get(i).map(|s| &s[..]).unwrap_or("");
+                    let right = d.lines.get(i).map(|s| &s[..]).unwrap_or("");
+                    let joint = if i == 0 { "-" } else { " " };
+                    format!("{}{joint}{right}", pad(left, acc.width, ' '))
+                })
+                .collect();
+            acc = Self {
+                lines,
+                width: acc.width + 1 + d.width,
+            };
+        }
+        acc
+    }
+
+    fn choice(ds: Vec<Self>) -> Self {
+        let width = ds.iter().map(|d| d.width).max().unwrap_or(0);
+        let mut lines = vec![];
+        for (n, d) in ds.iter().enumerate() {
+            let last = n + 1 == ds.len();
+            for (i, l) in d.lines.iter().enumerate() {
+                lines.push(match (n, i) {
+                    (0, 0) => format!("-+-{}-+-", pad(l, width, '-')),
+                    (_, 0) => format!(" +-{}-+ ", pad(l, width, '-')),
+                    _ if last => format!("   {}   ", pad(l, width, ' ')),
+                    _ => format!(" | {} | ", pad(l, width, ' ')),
+                });
+            }
+        }
+        Self {
+            lines,
+            width: width + 6,
+        }
+    }
+
+    fn repeat(item: Self, sep: Option<Self>, bounds: Option<String>) -> Self {
+        let mut sep = sep.unwrap_or_else(|| Self::atom(String::new()));
+        if let Some(bounds) = bounds {
+            sep.lines[0] = format!("{}{{{bounds}}}", pad(&sep.lines[0], sep.width, '-'));
+            sep.width += bounds.chars().count() + 2;
+        }
+        let width = std::cmp::max(item.width, sep.width);
+        let mut lines = vec![];
+        for (i, l) in item.lines.iter().enumerate() {
+            if i == 0 {
+                lines.push(format!("-+-{}-+-", pad(l, width, '-')));
+            } else {
+                lines.push(format!(" | {} | ", pad(l, width, ' ')));
+            }
+        }
+        for (i, l) in sep.lines.iter().enumerate() {
+            if i == 0 {
+                lines.push(format!(" +<{}-+ ", pad(l, width, '-')));
+            } else {
+                lines.push(format!("   {}   ", pad(l, width, ' ')));
+            }
+        }
+        Self {
+            lines,
+            width: width + 6,
+        }
+    }
+}
@@ -1,17 +1,24 @@
 #![feature(type_alias_impl_trait, impl_trait_in_assoc_type)]
 
 use combine::eof;
+use combine::error::StreamError as _;
 use combine::parser::char::{alpha_num, spaces};
-use combine::stream::{easy, position};
+use combine::stream::{easy, position, StreamErrorFor};
+pub use grammar::{Grammar, HasGrammar};
 use prelude::*;
 use std::convert::Infallible;
 use std::marker::PhantomData;
 use std::{
-    io, iter, num,
+    borrow::Cow,
+    fmt, io, iter, num,
     ops::{Deref, DerefMut},
     slice, str, vec,
 };
 
+mod grammar;
+#[cfg(feature = "trace")]
+pub mod trace;
+
 pub mod prelude {
     pub use super::*;
     pub use combine::parser::char::*;
@@ -37,6 +44,12 @@ impl HasParser for char {
     }
 }
 
+impl HasGrammar for char {
+    fn grammar() -> Grammar {
+        Grammar::Terminal("alpha_num")
+    }
+}
+
 impl<A, B> HasParser for (A, B)
 where
     A: HasParser,
@@ -48,6 +61,16 @@ where
     }
 }
 
+impl<A, B> HasGrammar for (A, B)
+where
+    A: HasGrammar,
+    B: HasGrammar,
+{
+    fn grammar() -> Grammar {
+        Grammar::Sequence(vec![A::grammar(), B::grammar()])
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     ParseInt(num::ParseIntError),
@@ -79,6 +102,31 @@ impl From<easy::Errors<char, &str, position::SourcePosition>> for Error {
     }
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseInt(e) => write!(f, "{e}"),
+            Self::Io(e) => write!(f, "{e}"),
+            Self::ParseError(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error {
+    pub fn with_grammar<T: HasGrammar>(
+        input: &str,
+        e: easy::Errors<char, &str, position::SourcePosition>,
+    ) -> Self {
+        let pos = e.position;
+        let line = input.lines().nth(pos.line as usize - 1).unwrap_or("");
+        let caret = " ".repeat(pos.column as usize - 1);
+        Self::ParseError(format!(
+            "{e}{line}\n{caret}^\nexpected input shape:\n{}",
+            T::grammar().ebnf()
+        ))
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 macro_rules! unsigned_number_parser {
@@ -88,6 +136,12 @@ macro_rules! unsigned_number_parser {
             fn parser() -> _ {
                 many1(digit()).map(|s: String| s.parse::<Self>().unwrap())
             }
+        }
+
+        impl HasGrammar for $id {
+            fn grammar() -> Grammar {
+                Grammar::Terminal("digits")
+            }
         })*
     }
 }
@@ -105,6 +159,12 @@ macro_rules! signed_number_parser {
                     u32::parser().map(|v| v.try_into().unwrap())
                 ))
             }
+        }
+
+        impl HasGrammar for $id {
+            fn grammar() -> Grammar {
+                Grammar::Terminal("integer")
+            }
         })*
     }
 }
@@ -118,6 +178,12 @@ impl HasParser for String {
     }
 }
 
+impl HasGrammar for String {
+    fn grammar() -> Grammar {
+        Grammar::Terminal("any")
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Comma;
 
@@ -136,61 +202,179 @@ pub struct SepBy<T>(PhantomData<T>);
 #[derive(Debug, Clone, Copy)]
 pub struct TermWith<T>(PhantomData<T>);
 
-#[derive(Clone, Debug)]
-pub struct List<T, Sep>(Vec<T>, PhantomData<Sep>);
+#[derive(Debug)]
+pub struct List<T, Sep, Len = OneOrMore>(Vec<T>, PhantomData<(Sep, Len)>);
+
+impl<T: Clone, Sep, Len> Clone for List<T, Sep, Len> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Nil;
 
-impl<T, Sep> From<Vec<T>> for List<T, Sep> {
+/// The number of items a `List` accepts. `usize::MAX` means no upper limit.
+pub trait LengthBound {
+    const MIN: usize;
+    const MAX: usize;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OneOrMore;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ZeroOrMore;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Exactly<const N: usize>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Between<const MIN: usize, const MAX: usize>;
+
+impl LengthBound for OneOrMore {
+    const MIN: usize = 1;
+    const MAX: usize = usize::MAX;
+}
+
+impl LengthBound for ZeroOrMore {
+    const MIN: usize = 0;
+    const MAX: usize = usize::MAX;
+}
+
+impl<const N: usize> LengthBound for Exactly<N> {
+    const MIN: usize = N;
+    const MAX: usize = N;
+}
+
+impl<const MIN: usize, const MAX: usize> LengthBound for Between<MIN, MAX> {
+    const MIN: usize = MIN;
+    const MAX: usize = MAX;
+}
+
+impl<T, Sep, Len> From<Vec<T>> for List<T, Sep, Len> {
     fn from(v: Vec<T>) -> Self {
         Self(v, PhantomData)
     }
 }
 
-impl<T: HasParser> HasParser for List<T, Nil> {
-    #[into_parser]
-    fn parser() -> _ {
-        many1(T::parser()).map(|v: Vec<_>| v.into())
+macro_rules! separator_parser {
+    ($($id:ty => $s:literal),*) => {
+        $(impl HasParser for $id {
+            #[into_parser]
+            fn parser() -> _ {
+                string($s).map(|_| Self)
+            }
+        }
+
+        impl HasGrammar for $id {
+            fn grammar() -> Grammar {
+                Grammar::literal($s)
+            }
+        })*
     }
 }
 
-impl<T: HasParser> HasParser for List<T, SepBy<Comma>> {
+separator_parser!(Comma => ",", CommaSpace => ", ", NewLine => "\n", Space => " ");
+
+impl<T, Sep, Len: LengthBound> HasParser for List<T, Sep, Len>
+where
+    Self: HasLengthParser,
+{
     #[into_parser]
     fn parser() -> _ {
-        sep_by1(T::parser(), token(',')).map(|v: Vec<_>| v.into())
+        Self::length_parser(Len::MIN, Len::MAX)
     }
 }
 
-impl<T: HasParser> HasParser for List<T, SepBy<CommaSpace>> {
-    #[into_parser]
-    fn parser() -> _ {
-        sep_by1(T::parser(), string(", ")).map(|v: Vec<_>| v.into())
+impl<T: HasGrammar, Len: LengthBound> HasGrammar for List<T, Nil, Len> {
+    fn grammar() -> Grammar {
+        Grammar::repeat_bounded(T::grammar(), None, Len::MIN, Len::MAX)
     }
 }
 
-impl<T: HasParser> HasParser for List<T, SepBy<NewLine>> {
-    #[into_parser]
-    fn parser() -> _ {
-        sep_by1(T::parser(), token('\n')).map(|v: Vec<_>| v.into())
+impl<T: HasGrammar, S: HasGrammar, Len: LengthBound> HasGrammar for List<T, SepBy<S>, Len> {
+    fn grammar() -> Grammar {
+        Grammar::repeat_bounded(T::grammar(), Some(S::grammar()), Len::MIN, Len::MAX)
     }
 }
 
-impl<T: HasParser> HasParser for List<T, TermWith<NewLine>> {
-    #[into_parser]
-    fn parser() -> _ {
-        many1(T::parser().skip(token('\n'))).map(|v: Vec<_>| v.into())
+impl<T: HasGrammar, S: HasGrammar, Len: LengthBound> HasGrammar for List<T, TermWith<S>, Len> {
+    fn grammar() -> Grammar {
+        let item = Grammar::Sequence(vec![T::grammar(), S::grammar()]);
+        Grammar::repeat_bounded(item, None, Len::MIN, Len::MAX)
     }
 }
 
-impl<T: HasParser> HasParser for List<T, SepBy<Space>> {
-    #[into_parser]
-    fn parser() -> _ {
-        sep_by1(T::parser(), token(' ')).map(|v: Vec<_>| v.into())
+/// Parsers for a number of items only known at runtime, like a list preceded by its length.
+pub trait HasLengthParser: Sized {
+    fn length_parser<Input>(min: usize, max: usize) -> impl Parser<Input, Output = Self>
+    where
+        Input: combine::Stream<Token = char>;
+}
+
+/// Lists are parsed to one item past `max`, so too long a list is reported here rather than as
+/// whatever follows it being unexpected.
+fn check_length<Input: combine::Stream, T, Sep, Len>(
+    v: Vec<T>,
+    min: usize,
+    max: usize,
+) -> std::result::Result<List<T, Sep, Len>, StreamErrorFor<Input>> {
+    if v.len() > max {
+        Err(StreamErrorFor::<Input>::message_format(format!(
+            "expected at most {max} items, found more"
+        )))
+    } else if v.len() < min {
+        let expected =
+            grammar::describe_bounds(min, max).unwrap_or_else(|| format!("at least {min}"));
+        Err(StreamErrorFor::<Input>::message_format(format!(
+            "expected {expected} items, found {}",
+            v.len()
+        )))
+    } else {
+        Ok(v.into())
+    }
+}
+
+impl<T: HasParser, Len> HasLengthParser for List<T, Nil, Len> {
+    fn length_parser<Input>(min: usize, max: usize) -> impl Parser<Input, Output = Self>
+    where
+        Input: combine::Stream<Token = char>,
+    {
+        count_min_max(0, max.saturating_add(1), T::parser())
+            .and_then(move |v| check_length::<Input, _, _, _>(v, min, max))
     }
 }
 
-impl<T, Sep> List<T, Sep> {
+impl<T: HasParser, S: HasParser, Len> HasLengthParser for List<T, SepBy<S>, Len> {
+    fn length_parser<Input>(min: usize, max: usize) -> impl Parser<Input, Output = Self>
+    where
+        Input: combine::Stream<Token = char>,
+    {
+        let rest = count_min_max(0, max, S::parser().with(T::parser()));
+        optional((T::parser(), rest))
+            .map(|items: Option<(T, Vec<T>)>| match items {
+                Some((first, mut rest)) => {
+                    rest.insert(0, first);
+                    rest
+                }
+                None => vec![],
+            })
+            .and_then(move |v| check_length::<Input, _, _, _>(v, min, max))
+    }
+}
+
+impl<T: HasParser, S: HasParser, Len> HasLengthParser for List<T, TermWith<S>, Len> {
+    fn length_parser<Input>(min: usize, max: usize) -> impl Parser<Input, Output = Self>
+    where
+        Input: combine::Stream<Token = char>,
+    {
+        count_min_max(0, max.saturating_add(1), T::parser().skip(S::parser()))
+            .and_then(move |v| check_length::<Input, _, _, _>(v, min, max))
+    }
+}
+
+impl<T, Sep, Len> List<T, Sep, Len> {
     pub fn new() -> Self {
         Self(vec![], PhantomData)
     }
@@ -216,7 +400,7 @@ impl<T, Sep> List<T, Sep> {
     }
 }
 
-impl<'a, T, Sep> IntoIterator for &'a List<T, Sep> {
+impl<'a, T, Sep, Len> IntoIterator for &'a List<T, Sep, Len> {
     type Item = &'a T;
     type IntoIter = slice::Iter<'a, T>;
 
@@ -225,7 +409,7 @@ impl<'a, T, Sep> IntoIterator for &'a List<T, Sep> {
     }
 }
 
-impl<'a, T, Sep> IntoIterator for &'a mut List<T, Sep> {
+impl<'a, T, Sep, Len> IntoIterator for &'a mut List<T, Sep, Len> {
     type Item = &'a mut T;
     type IntoIter = slice::IterMut<'a, T>;
 
@@ -234,7 +418,7 @@ impl<'a, T, Sep> IntoIterator for &'a mut List<T, Sep> {
     }
 }
 
-impl<T, Sep> IntoIterator for List<T, Sep> {
+impl<T, Sep, Len> IntoIterator for List<T, Sep, Len> {
     type Item = T;
     type IntoIter = vec::IntoIter<T>;
 
@@ -243,25 +427,33 @@ impl<T, Sep> IntoIterator for List<T, Sep> {
     }
 }
 
-impl<T, Sep> iter::FromIterator<T> for List<T, Sep> {
+impl<T, Sep, Len> iter::FromIterator<T> for List<T, Sep, Len> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         Self(iter::FromIterator::from_iter(iter), PhantomData)
     }
 }
 
-impl<T, Sep> AsRef<[T]> for List<T, Sep> {
+impl<T, Sep, Len> AsRef<[T]> for List<T, Sep, Len> {
     fn as_ref(&self) -> &[T] {
         self.0.as_ref()
     }
 }
 
-impl<T, Sep> AsMut<[T]> for List<T, Sep> {
+impl<T, Sep, Len> AsMut<[T]> for List<T, Sep, Len> {
     fn as_mut(&mut self) -> &mut [T] {
         self.0.as_mut()
     }
 }
 
-impl<T, Sep> Deref for List<T, Sep> {
+impl<T: PartialEq, Sep, Len> PartialEq for List<T, Sep, Len> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq, Sep, Len> Eq for List<T, Sep, Len> {}
+
+impl<T, Sep, Len> Deref for List<T, Sep, Len> {
     type Target = [T];
 
     fn deref(&self) -> &[T] {
@@ -269,7 +461,7 @@ impl<T, Sep> Deref for List<T, Sep> {
     }
 }
 
-impl<T, Sep> DerefMut for List<T, Sep> {
+impl<T, Sep, Len> DerefMut for List<T, Sep, Len> {
     fn deref_mut(&mut self) -> &mut [T] {
         self.0.deref_mut()
     }
@@ -284,3 +476,70 @@ pub fn parse_str<T: HasParser>(
         .easy_parse(position::Stream::new(input))?;
     Ok(t)
 }
+
+/// What to do about the newline at the end of an input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TrailingNewline {
+    /// Leave the end alone.
+    Keep,
+    /// End a non-empty input with exactly one newline.
+    #[default]
+    Ensure,
+    /// Remove all newlines from the end.
+    Strip,
+}
+
+/// Clean-ups applied to input before parsing, so the same input saved with a byte order mark,
+/// Windows line endings or without a final newline parses the same way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Normalise {
+    pub strip_bom: bool,
+    /// Turn `\r\n` into `\n`.
+    pub crlf_to_lf: bool,
+    pub trailing_newline: TrailingNewline,
+}
+
+impl Default for Normalise {
+    fn default() -> Self {
+        Self {
+            strip_bom: true,
+            crlf_to_lf: true,
+            trailing_newline: TrailingNewline::Ensure,
+        }
+    }
+}
+
+impl Normalise {
+    /// Leaves input as it is.
+    pub const NONE: Self = Self {
+        strip_bom: false,
+        crlf_to_lf: false,
+        trailing_newline: TrailingNewline::Keep,
+    };
+
+    pub fn apply<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        let mut input = Cow::Borrowed(input);
+        if self.strip_bom {
+            if let Some(rest) = input.strip_prefix('\u{feff}') {
+                input = rest.to_owned().into();
+            }
+        }
+        if self.crlf_to_lf && input.contains("\r\n") {
+            input = input.replace("\r\n", "\n").into();
+        }
+        let body = input.trim_end_matches('\n');
+        match self.trailing_newline {
+            TrailingNewline::Keep => input,
+            TrailingNewline::Ensure if body.is_empty() || input.len() == body.len() + 1 => input,
+            TrailingNewline::Ensure => format!("{body}\n").into(),
+            TrailingNewline::Strip if input.len() == body.len() => input,
+            TrailingNewline::Strip => body.to_owned().into(),
+        }
+    }
+}
+
+/// Like [`parse_str`], after normalising the input.
+pub fn parse_str_with<T: HasParser>(options: &Normalise, input: &str) -> Result<T> {
+    let input = options.apply(input);
+    Ok(parse_str(&input)?)
+}
@@ -0,0 +1,206 @@
+use combine::error::{ParseResult, Tracked};
+use combine::stream::easy;
+use combine::stream::position::{self, Positioner, SourcePosition};
+use combine::{
+    eof, parser::char::spaces, EasyParser as _, ErrorOffset, Parser, Stream, StreamOnce,
+};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
+use super::HasParser;
+
+thread_local! {
+    static POSITION: Cell<SourcePosition> = Cell::new(SourcePosition::default());
+    static STATE: RefCell<Option<State>> = const { RefCell::new(None) };
+}
+
+#[derive(Default)]
+struct State {
+    stack: Vec<TraceNode>,
+    roots: Vec<TraceNode>,
+}
+
+fn enter(type_name: &'static str, item: Option<&'static str>) {
+    STATE.with(|s| {
+        if let Some(state) = &mut *s.borrow_mut() {
+            let start = POSITION.get();
+            state.stack.push(TraceNode {
+                type_name,
+                item,
+                start,
+                end: start,
+                success: false,
+                children: vec![],
+            });
+        }
+    })
+}
+
+fn exit(success: bool) {
+    STATE.with(|s| {
+        if let Some(state) = &mut *s.borrow_mut() {
+            let mut node = state.stack.pop().unwrap();
+            node.end = POSITION.get();
+            node.success = success;
+            match state.stack.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => state.roots.push(node),
+            }
+        }
+    })
+}
+
+/// Wraps `SourcePosition` so the current position is visible to `Traced` parsers, which are
+/// generic over the input and can't ask it directly.
+#[derive(Clone, Copy, Default)]
+pub struct TracePositioner(SourcePosition);
+
+impl Positioner<char> for TracePositioner {
+    type Position = SourcePosition;
+    type Checkpoint = SourcePosition;
+
+    fn position(&self) -> SourcePosition {
+        self.0
+    }
+
+    fn update(&mut self, token: &char) {
+        self.0.update(token);
+        POSITION.set(self.0);
+    }
+
+    fn checkpoint(&self) -> SourcePosition {
+        self.0
+    }
+
+    fn reset(&mut self, checkpoint: SourcePosition) {
+        self.0 = checkpoint;
+        POSITION.set(self.0);
+    }
+}
+
+#[derive(Clone)]
+pub struct Traced<P> {
+    parser: P,
+    type_name: &'static str,
+    item: Option<&'static str>,
+}
+
+impl<Input, P> Parser<Input> for Traced<P>
+where
+    Input: Stream,
+    P: Parser<Input>,
+{
+    type Output = P::Output;
+    type PartialState = P::PartialState;
+
+    fn parse_first(
+        &mut self,
+        input: &mut Input,
+        state: &mut Self::PartialState,
+    ) -> ParseResult<Self::Output, <Input as StreamOnce>::Error> {
+        enter(self.type_name, self.item);
+        let result = self.parser.parse_first(input, state);
+        exit(result.is_ok());
+        result
+    }
+
+    fn parse_partial(
+        &mut self,
+        input: &mut Input,
+        state: &mut Self::PartialState,
+    ) -> ParseResult<Self::Output, <Input as StreamOnce>::Error> {
+        enter(self.type_name, self.item);
+        let result = self.parser.parse_partial(input, state);
+        exit(result.is_ok());
+        result
+    }
+
+    fn add_error(&mut self, errors: &mut Tracked<<Input as StreamOnce>::Error>) {
+        self.parser.add_error(errors)
+    }
+
+    fn add_committed_expected_error(&mut self, errors: &mut Tracked<<Input as StreamOnce>::Error>) {
+        self.parser.add_committed_expected_error(errors)
+    }
+
+    fn parser_count(&self) -> ErrorOffset {
+        self.parser.parser_count()
+    }
+}
+
+pub fn traced<Input, P>(type_name: &'static str, item: Option<&'static str>, parser: P) -> Traced<P>
+where
+    Input: Stream,
+    P: Parser<Input>,
+{
+    Traced {
+        parser,
+        type_name,
+        item,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TraceNode {
+    pub type_name: &'static str,
+    pub item: Option<&'static str>,
+    pub start: SourcePosition,
+    pub end: SourcePosition,
+    pub success: bool,
+    pub children: Vec<TraceNode>,
+}
+
+impl TraceNode {
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:width$}{}", "", self.type_name, width = depth * 2)?;
+        if let Some(item) = self.item {
+            write!(f, "::{item}")?;
+        }
+        writeln!(
+            f,
+            " {}:{}..{}:{} {}",
+            self.start.line,
+            self.start.column,
+            self.end.line,
+            self.end.column,
+            if self.success { "ok" } else { "failed" }
+        )?;
+        for c in &self.children {
+            c.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Trace {
+    pub roots: Vec<TraceNode>,
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in &self.roots {
+            r.fmt_indented(f, 0)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_str_traced<T: HasParser>(
+    input: &str,
+) -> (Result<T, easy::Errors<char, &str, SourcePosition>>, Trace) {
+    STATE.with(|s| *s.borrow_mut() = Some(State::default()));
+    POSITION.set(SourcePosition::default());
+
+    let result = T::parser()
+        .skip(spaces())
+        .skip(eof())
+        .easy_parse(position::Stream::with_positioner(
+            input,
+            TracePositioner::default(),
+        ))
+        .map(|(t, _)| t);
+
+    let state = STATE.with(|s| s.borrow_mut().take()).unwrap();
+    (result, Trace { roots: state.roots })
+}
@@ -116,3 +116,205 @@ fn unit_struct() {
     test_parse(Foo, "foo");
     test_parse(Baz, "qux");
 }
+
+#[test]
+fn grammar_ebnf() {
+    assert_eq!(
+        <CustomCombineParse as HasGrammar>::grammar().ebnf(),
+        "CustomCombineParse = \"{ c: \", ? alpha_num ?, \", \", \"i: \", ? digits ?, \", \", \"o: \", \
+         ManyThings, \" }\" ;\n\
+         ManyThings = \"Z\" | \"salad\" | \"salsa\" ;\n"
+    );
+    assert_eq!(
+        <List<MixedThings, SepBy<Comma>> as HasGrammar>::grammar().ebnf(),
+        "input = MixedThings, { \",\", MixedThings } ;\n\
+         MixedThings = \"hello\" | \"(\", ? digits ?, \", \", ? digits ?, \")\" \
+         | ? digits ?, \"u\" | ? integer ?, \"i\" ;\n"
+    );
+}
+
+#[test]
+fn grammar_railroad() {
+    assert_eq!(
+        <List<ManyThings, SepBy<Space>> as HasGrammar>::grammar().railroad(),
+        "\
+input:
+>>--+-[ManyThings]-+--><
+    +<\" \"----------+
+ManyThings:
+>>--+-\"Z\"-----+--><
+    +-\"salad\"-+
+    +-\"salsa\"-+
+"
+    );
+}
+
+#[cfg(feature = "trace")]
+#[test]
+fn trace_enum_alternatives() {
+    let (result, trace) = parse::trace::parse_str_traced::<MixedThings>("-3i");
+    assert_eq!(result.unwrap(), MixedThings::SNum { i: -3 });
+    assert_eq!(
+        trace.to_string(),
+        "\
+MixedThings 1:1..1:4 ok
+  MixedThings::Hello 1:1..1:2 failed
+  MixedThings::Tuple 1:1..1:2 failed
+  MixedThings::UNum 1:1..1:2 failed
+    MixedThings::0 1:1..1:2 failed
+  MixedThings::SNum 1:1..1:4 ok
+    MixedThings::i 1:1..1:3 ok
+"
+    );
+}
+
+#[derive(Debug, PartialEq, HasParser)]
+enum PrefixThings {
+    #[parse(string = "a")]
+    A,
+    #[parse(before = "x")]
+    X(u32),
+    #[parse(string = "ab")]
+    Ab,
+    #[parse(before = "a:")]
+    Labeled(u32),
+    #[parse(before = "a")]
+    Numbered(u32),
+}
+
+#[test]
+fn longest_literal_first() {
+    test_parse(PrefixThings::A, "a");
+    test_parse(PrefixThings::Ab, "ab");
+    test_parse(PrefixThings::X(1), "x1");
+    test_parse(PrefixThings::Labeled(2), "a:2");
+    test_parse(PrefixThings::Numbered(3), "a3");
+}
+
+#[derive(Debug, PartialEq, HasParser)]
+struct Counted {
+    #[parse(after = " items:")]
+    n: u32,
+    #[parse(count = n)]
+    items: List<char, SepBy<CommaSpace>>,
+    #[parse(before = "then ")]
+    width: u32,
+    #[parse(count = width)]
+    row: List<char, Nil>,
+}
+
+#[test]
+fn count_prefixed_lists() {
+    test_parse(
+        Counted {
+            n: 3,
+            items: vec!['a', 'b', 'c'].into(),
+            width: 2,
+            row: vec!['x', 'y'].into(),
+        },
+        "3 items: a, b, c then 2 xy",
+    );
+    test_parse(
+        Counted {
+            n: 0,
+            items: vec![].into(),
+            width: 0,
+            row: vec![].into(),
+        },
+        "0 items:  then 0 ",
+    );
+
+    let err = parse::parse_str::<Counted>("3 items: a, b then 2 xy").unwrap_err();
+    assert!(
+        err.to_string().contains("expected 3 items, found 2"),
+        "{err}"
+    );
+    let err = parse::parse_str::<Counted>("2 items: a, b, c then 2 xy").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("expected at most 2 items, found more"),
+        "{err}"
+    );
+}
+
+#[derive(Debug, PartialEq, HasParser)]
+#[parse(sep_by = "|")]
+struct Bounded {
+    any: List<u32, SepBy<Comma>, ZeroOrMore>,
+    three: List<char, Nil, Exactly<3>>,
+    some: List<u32, SepBy<Space>, Between<1, 2>>,
+}
+
+#[test]
+fn bounded_lists() {
+    test_parse(
+        Bounded {
+            any: vec![].into(),
+            three: vec!['a', 'b', 'c'].into(),
+            some: vec![1, 2].into(),
+        },
+        "|abc|1 2",
+    );
+    test_parse(
+        Bounded {
+            any: vec![4, 5].into(),
+            three: vec!['x', 'y', 'z'].into(),
+            some: vec![1].into(),
+        },
+        "4,5|xyz|1",
+    );
+
+    let err = parse::parse_str::<Bounded>("|ab|1").unwrap_err();
+    assert!(
+        err.to_string().contains("expected 3 items, found 2"),
+        "{err}"
+    );
+    let err = parse::parse_str::<Bounded>("|abc|1 2 3").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("expected at most 2 items, found more"),
+        "{err}"
+    );
+    let err = parse::parse_str::<Bounded>("|abcd|1").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("expected at most 3 items, found more"),
+        "{err}"
+    );
+
+    assert_eq!(
+        Bounded::grammar().ebnf(),
+        "Bounded = [ ? digits ?, { \",\", ? digits ? } ], \"|\", \
+         ( ? alpha_num ?, { ? alpha_num ? } (* 3 items *) ), \"|\", \
+         ( ? digits ?, { \" \", ? digits ? } (* 1 to 2 items *) ) ;\n"
+    );
+}
+
+#[derive(Debug, PartialEq, HasParser)]
+struct Lines {
+    lines: List<List<u32, SepBy<Space>>, TermWith<NewLine>>,
+}
+
+#[test]
+fn normalised_input() {
+    let expected = Lines {
+        lines: vec![vec![1, 2].into(), vec![3].into()].into(),
+    };
+    let options = parse::Normalise::default();
+    for input in ["1 2\n3\n", "1 2\n3", "\u{feff}1 2\r\n3\r\n", "1 2\r\n3\n\n"] {
+        let actual: Lines = parse::parse_str_with(&options, input).unwrap();
+        assert_eq!(actual, expected, "{input:?}");
+    }
+    assert!(parse::parse_str::<Lines>("1 2\r\n3\r\n").is_err());
+
+    let strip = parse::Normalise {
+        trailing_newline: parse::TrailingNewline::Strip,
+        ..options
+    };
+    assert_eq!(strip.apply("a\r\nb\r\n\r\n"), "a\nb");
+    assert_eq!(options.apply(""), "");
+    assert_eq!(
+        parse::Normalise::NONE.apply("\u{feff}a\r\n"),
+        "\u{feff}a\r\n"
+    );
+}
@@ -0,0 +1,12 @@
+#![feature(type_alias_impl_trait, impl_trait_in_assoc_type)]
+
+use parse::prelude::*;
+
+// Only `count` names a field; other keywords take a string.
+#[derive(HasParser)]
+struct Labelled {
+    #[parse(before = label)]
+    n: u32,
+}
+
+fn main() {}
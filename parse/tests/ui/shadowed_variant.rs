@@ -0,0 +1,15 @@
+#![feature(type_alias_impl_trait, impl_trait_in_assoc_type)]
+
+use parse::prelude::*;
+
+// `Ab` can't be moved before `A` without also going before `Number`, which could match its input.
+#[derive(HasParser)]
+enum Shadowed {
+    #[parse(string = "a")]
+    A,
+    Number(u32),
+    #[parse(string = "ab")]
+    Ab,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#![feature(type_alias_impl_trait, impl_trait_in_assoc_type)]
+
+use parse::prelude::*;
+
+#[derive(HasParser)]
+enum Repeated {
+    #[parse(string = "a")]
+    A,
+    #[parse(string = "a")]
+    AlsoA,
+}
+
+fn main() {}
@@ -5,7 +5,12 @@ use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::*;
 
-pub trait AttrKeywordKind: TryFrom<Ident, Error = Error> + PartialOrd + Ord {}
+pub trait AttrKeywordKind: TryFrom<Ident, Error = Error> + PartialOrd + Ord {
+    /// Whether the keyword's value can be a bare identifier, naming something, as well as a string.
+    fn takes_ident(&self) -> bool {
+        false
+    }
+}
 
 struct ParseAttrs<Kind> {
     _parens: token::Paren,
@@ -26,9 +31,14 @@ impl<Kind: AttrKeywordKind> Parse for ParseAttrs<Kind> {
 pub enum FieldKeyword {
     Before,
     After,
+    Count,
 }
 
-impl AttrKeywordKind for FieldKeyword {}
+impl AttrKeywordKind for FieldKeyword {
+    fn takes_ident(&self) -> bool {
+        *self == Self::Count
+    }
+}
 
 impl TryFrom<Ident> for FieldKeyword {
     type Error = Error;
@@ -37,6 +47,7 @@ impl TryFrom<Ident> for FieldKeyword {
         Ok(match &id.to_string()[..] {
             "before" => Self::Before,
             "after" => Self::After,
+            "count" => Self::Count,
             _ => return Err(Error::new(id.span(), "unknown keyword")),
         })
     }
@@ -112,10 +123,16 @@ struct ParseAttr<Kind> {
 
 impl<Kind: AttrKeywordKind> Parse for ParseAttr<Kind> {
     fn parse(input: ParseStream) -> Result<Self> {
+        let kw: AttrKeyword<Kind> = input.parse()?;
         Ok(Self {
-            kw: input.parse()?,
             _equal_token: input.parse()?,
-            value: input.parse()?,
+            value: if kw.kind.takes_ident() && input.peek(Ident) {
+                let id: Ident = input.parse()?;
+                LitStr::new(&id.to_string(), id.span())
+            } else {
+                input.parse()?
+            },
+            kw,
         })
     }
 }
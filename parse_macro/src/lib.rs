@@ -9,6 +9,7 @@ use std::matches;
 use syn::*;
 
 mod attrs;
+mod shadowing;
 
 fn verify_signature(sig: &Signature) -> Result<()> {
     let as_expected = matches!(sig, Signature {
@@ -63,24 +64,58 @@ pub fn into_parser(_attr: TokenStream, input: TokenStream) -> TokenStream {
     }
 }
 
+#[cfg(feature = "trace")]
+fn traced(type_name: &Ident, item: Option<String>, parser: Expr) -> Expr {
+    let type_name = type_name.to_string();
+    let item: Expr = match item {
+        Some(item) => parse_quote!(Some(#item)),
+        None => parse_quote!(None),
+    };
+    parse_quote!(::parse::trace::traced(#type_name, #item, #parser))
+}
+
+#[cfg(not(feature = "trace"))]
+fn traced(_type_name: &Ident, _item: Option<String>, parser: Expr) -> Expr {
+    parser
+}
+
 fn derive_has_parser_struct(
     name: Ident,
     attrs: Vec<Attribute>,
     data: DataStruct,
-) -> Result<ItemImpl> {
-    let parser_expr = parse_expr_for_struct(parse_quote!(Self), name.clone(), attrs, data.fields)?;
-
-    Ok(parse_quote! {
-        impl ::parse::HasParser for #name {
-            #[into_parser]
-            fn parser() -> _ {
-                #parser_expr
+) -> Result<Vec<ItemImpl>> {
+    let parser_expr = parse_expr_for_struct(
+        &name,
+        parse_quote!(Self),
+        name.clone(),
+        attrs.clone(),
+        data.fields.clone(),
+    )?;
+    let parser_expr = traced(&name, None, parser_expr);
+    let grammar_expr = grammar_expr_for_struct(&name, attrs, data.fields)?;
+    let name_str = name.to_string();
+
+    Ok(vec![
+        parse_quote! {
+            impl ::parse::HasParser for #name {
+                #[into_parser]
+                fn parser() -> _ {
+                    #parser_expr
+                }
             }
-        }
-    })
+        },
+        parse_quote! {
+            impl ::parse::HasGrammar for #name {
+                fn grammar() -> ::parse::Grammar {
+                    ::parse::Grammar::rule(#name_str, || #grammar_expr)
+                }
+            }
+        },
+    ])
 }
 
 fn parse_expr_for_struct(
+    type_name: &Ident,
     self_expr: Expr,
     name: Ident,
     attrs: Vec<Attribute>,
@@ -89,39 +124,96 @@ fn parse_expr_for_struct(
     let fields: Vec<&Field> = fields.iter().collect();
 
     if fields.is_empty() {
-        let parser = get_unit_parser_from_attrs(name_parser(&name), attrs)?;
-        Ok(parse_quote!(#parser.map(|_| #self_expr)))
+        let literal = get_unit_literal_from_attrs(&name, attrs)?;
+        Ok(parse_quote!(string(#literal).map(|_| #self_expr)))
     } else {
         let mut patterns: Vec<Pat> = vec![];
         let mut field_names: Vec<Ident> = vec![];
         let mut parsers: Vec<Expr> = vec![];
-        let mut fields_iter = fields.iter().peekable();
+        let mut counted: Vec<(Ident, Vec<Expr>, Vec<Pat>)> = vec![];
+        let mut fields_iter = fields.iter().enumerate().peekable();
         let mut unique = (1..).map(|n| Ident::new(&format!("f{n}"), Span::call_site()));
 
         let attr_map = attrs::parse_attr_map::<attrs::ContainerKeyword>(attrs)?;
         let sep_parser: Expr = get_separator_parser_from_attrs(parse_quote!(char(' ')), &attr_map);
 
-        while let Some(f) = fields_iter.next() {
+        while let Some((i, f)) = fields_iter.next() {
             let ty = &f.ty;
 
-            let default_parser_expr = parse_quote!(<#ty as ::parse::HasParser>::parser());
+            let field_map = attrs::parse_attr_map::<attrs::FieldKeyword>(f.attrs.clone())?;
+            let count = match field_map.get(&attrs::FieldKeyword::Count) {
+                Some(count) => {
+                    let count = Ident::new(&count.value(), count.span());
+                    if !field_names.contains(&count) {
+                        return Err(Error::new(
+                            count.span(),
+                            format!("count must name an earlier field, `{count}` isn't one"),
+                        ));
+                    }
+                    Some(count)
+                }
+                None => None,
+            };
+
+            let default_parser_expr = if count.is_some() {
+                parse_quote!(<#ty as ::parse::HasLengthParser>::length_parser(__count, __count))
+            } else {
+                parse_quote!(<#ty as ::parse::HasParser>::parser())
+            };
             let parser_expr = get_field_parser_from_attrs(default_parser_expr, f.attrs.clone())?;
-            if fields_iter.peek().is_some() {
-                parsers.push(parse_quote!(#parser_expr.skip(#sep_parser)));
+            let item = f
+                .ident
+                .as_ref()
+                .map(|i| i.to_string())
+                .unwrap_or(i.to_string());
+            let parser_expr = traced(type_name, Some(item), parser_expr);
+            let parser_expr = if fields_iter.peek().is_some() {
+                parse_quote!(#parser_expr.skip(#sep_parser))
             } else {
-                parsers.push(parser_expr);
-            }
+                parser_expr
+            };
 
-            if let Some(field_name) = f.ident.clone() {
-                patterns.push(parse_quote!(#field_name));
-                field_names.push(field_name);
+            let pattern: Pat = if let Some(field_name) = f.ident.clone() {
+                field_names.push(field_name.clone());
+                parse_quote!(#field_name)
             } else {
                 let ident = unique.next().unwrap();
-                patterns.push(parse_quote!(#ident));
+                parse_quote!(#ident)
+            };
+
+            if let Some(count) = count {
+                counted.push((count, vec![], vec![]));
             }
+            match counted.last_mut() {
+                Some((_, parsers, patterns)) => {
+                    parsers.push(parser_expr);
+                    patterns.push(pattern);
+                }
+                None => {
+                    parsers.push(parser_expr);
+                    patterns.push(pattern);
+                }
+            }
+        }
+
+        // Fields after a counted list are parsed by a parser built from the values before it, which
+        // are carried through and flattened back into one tuple.
+        let mut parser_expr: Expr = parse_quote!((#(#parsers),*));
+        for (count, seg_parsers, seg_patterns) in counted {
+            parser_expr = parse_quote! {
+                #parser_expr.then(move |(#(#patterns),*)| {
+                    let __count = #count as usize;
+                    let mut __carry = Some((#(#patterns),*));
+                    (#(#seg_parsers),*).map(move |(#(#seg_patterns),*)| {
+                        let (#(#patterns),*) = __carry.take().unwrap();
+                        (#(#patterns,)* #(#seg_patterns),*)
+                    })
+                })
+            };
+            patterns.extend(seg_patterns);
         }
 
-        let parser_expr = get_struct_parser_from_attrs(parse_quote!((#(#parsers),*)), &attr_map);
+        let parser_expr = get_struct_parser_from_attrs(parser_expr, &attr_map);
 
         let map_closure: Expr = if field_names.is_empty() {
             if patterns.len() == 1 {
@@ -139,13 +231,71 @@ fn parse_expr_for_struct(
     }
 }
 
-fn get_unit_parser_from_attrs(default_parser: Expr, attrs: Vec<syn::Attribute>) -> Result<Expr> {
+fn grammar_sequence(mut items: Vec<Expr>) -> Expr {
+    if items.len() == 1 {
+        items.pop().unwrap()
+    } else {
+        parse_quote!(::parse::Grammar::Sequence(vec![#(#items),*]))
+    }
+}
+
+fn grammar_expr_for_struct(name: &Ident, attrs: Vec<Attribute>, fields: Fields) -> Result<Expr> {
+    if fields.is_empty() {
+        let literal = get_unit_literal_from_attrs(name, attrs)?;
+        return Ok(parse_quote!(::parse::Grammar::literal(#literal)));
+    }
+
+    let attr_map = attrs::parse_attr_map::<attrs::ContainerKeyword>(attrs)?;
+    let sep = attr_map
+        .get(&attrs::ContainerKeyword::SepBy)
+        .cloned()
+        .unwrap_or_else(|| LitStr::new(" ", Span::call_site()));
+
+    let mut items: Vec<Expr> = vec![];
+    if let Some(value) = attr_map.get(&attrs::ContainerKeyword::Before) {
+        items.push(parse_quote!(::parse::Grammar::literal(#value)));
+    }
+
+    let mut fields_iter = fields.iter().peekable();
+    while let Some(f) = fields_iter.next() {
+        let ty = &f.ty;
+        let field_map = attrs::parse_attr_map::<attrs::FieldKeyword>(f.attrs.clone())?;
+
+        if let Some(value) = field_map.get(&attrs::FieldKeyword::Before) {
+            items.push(parse_quote!(::parse::Grammar::literal(#value)));
+        }
+
+        let grammar: Expr = parse_quote!(<#ty as ::parse::HasGrammar>::grammar());
+        if let Some(field_name) = &f.ident {
+            let field_name = field_name.to_string();
+            items.push(parse_quote!(::parse::Grammar::field(#field_name, #grammar)));
+        } else {
+            items.push(grammar);
+        }
+
+        if let Some(value) = field_map.get(&attrs::FieldKeyword::After) {
+            items.push(parse_quote!(::parse::Grammar::literal(#value)));
+        }
+
+        if fields_iter.peek().is_some() {
+            items.push(parse_quote!(::parse::Grammar::literal(#sep)));
+        }
+    }
+
+    if let Some(value) = attr_map.get(&attrs::ContainerKeyword::After) {
+        items.push(parse_quote!(::parse::Grammar::literal(#value)));
+    }
+
+    Ok(grammar_sequence(items))
+}
+
+fn get_unit_literal_from_attrs(name: &Ident, attrs: Vec<syn::Attribute>) -> Result<LitStr> {
     let attr_map = attrs::parse_attr_map::<attrs::VariantKeyword>(attrs)?;
 
     if let Some(value) = attr_map.get(&attrs::VariantKeyword::String) {
-        Ok(parse_quote!(string(#value)))
+        Ok(value.clone())
     } else {
-        Ok(default_parser)
+        Ok(LitStr::new(&name.to_string().to_snake_case(), name.span()))
     }
 }
 
@@ -193,29 +343,50 @@ fn get_field_parser_from_attrs(default_parser: Expr, attrs: Vec<syn::Attribute>)
     Ok(parser)
 }
 
-fn name_parser(name: &Ident) -> Expr {
-    let name = name.to_string().to_snake_case();
-    parse_quote!(string(#name))
-}
-
-fn derive_has_parser_enum(name: Ident, data: DataEnum) -> Result<ItemImpl> {
+fn derive_has_parser_enum(name: Ident, data: DataEnum) -> Result<Vec<ItemImpl>> {
     let mut parsers: Vec<Expr> = vec![];
-    for v in data.variants {
-        let name = v.ident;
-        let parser = parse_expr_for_struct(parse_quote!(Self::#name), name, v.attrs, v.fields)?;
+    let mut grammars: Vec<Expr> = vec![];
+    for v in shadowing::order_variants(data.variants)? {
+        let variant = v.ident;
+        grammars.push(grammar_expr_for_struct(
+            &variant,
+            v.attrs.clone(),
+            v.fields.clone(),
+        )?);
+        let parser = parse_expr_for_struct(
+            &name,
+            parse_quote!(Self::#variant),
+            variant.clone(),
+            v.attrs,
+            v.fields,
+        )?;
+        let parser = traced(&name, Some(variant.to_string()), parser);
         parsers.push(parse_quote!(attempt(#parser)));
     }
-    Ok(parse_quote! {
-        impl ::parse::HasParser for #name {
-            #[into_parser]
-            fn parser() -> _ {
-                choice((#(#parsers),*))
+    let name_str = name.to_string();
+    let parser_expr = traced(&name, None, parse_quote!(choice((#(#parsers),*))));
+    Ok(vec![
+        parse_quote! {
+            impl ::parse::HasParser for #name {
+                #[into_parser]
+                fn parser() -> _ {
+                    #parser_expr
+                }
             }
-        }
-    })
+        },
+        parse_quote! {
+            impl ::parse::HasGrammar for #name {
+                fn grammar() -> ::parse::Grammar {
+                    ::parse::Grammar::rule(#name_str, || {
+                        ::parse::Grammar::Choice(vec![#(#grammars),*])
+                    })
+                }
+            }
+        },
+    ])
 }
 
-fn derive_has_parser_inner(input: DeriveInput) -> Result<ItemImpl> {
+fn derive_has_parser_inner(input: DeriveInput) -> Result<Vec<ItemImpl>> {
     match input.data {
         Data::Struct(ds) => derive_has_parser_struct(input.ident, input.attrs, ds),
         Data::Enum(de) => derive_has_parser_enum(input.ident, de),
@@ -227,7 +398,7 @@ fn derive_has_parser_inner(input: DeriveInput) -> Result<ItemImpl> {
 pub fn derive_has_parser(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     match derive_has_parser_inner(input) {
-        Ok(v) => quote!(#v).into(),
+        Ok(v) => quote!(#(#v)*).into(),
         Err(e) => e.into_compile_error().into(),
     }
 }
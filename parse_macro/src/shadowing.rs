@@ -0,0 +1,99 @@
+use crate::attrs;
+use heck::ToSnakeCase as _;
+use syn::*;
+
+/// The literals a variant's parser must start with.
+struct Prefix {
+    /// Set for unit variants, which match exactly this literal and nothing else.
+    exact: Option<String>,
+    leading: String,
+}
+
+fn prefix(v: &Variant) -> Result<Prefix> {
+    if v.fields.is_empty() {
+        let attr_map = attrs::parse_attr_map::<attrs::VariantKeyword>(v.attrs.clone())?;
+        let literal = attr_map
+            .get(&attrs::VariantKeyword::String)
+            .map(|s| s.value())
+            .unwrap_or_else(|| v.ident.to_string().to_snake_case());
+        return Ok(Prefix {
+            exact: Some(literal.clone()),
+            leading: literal,
+        });
+    }
+
+    let attr_map = attrs::parse_attr_map::<attrs::ContainerKeyword>(v.attrs.clone())?;
+    let mut leading = attr_map
+        .get(&attrs::ContainerKeyword::Before)
+        .map(|s| s.value())
+        .unwrap_or_default();
+
+    let first = v.fields.iter().next().unwrap();
+    let field_map = attrs::parse_attr_map::<attrs::FieldKeyword>(first.attrs.clone())?;
+    if let Some(before) = field_map.get(&attrs::FieldKeyword::Before) {
+        leading += &before.value();
+    }
+
+    Ok(Prefix {
+        exact: None,
+        leading,
+    })
+}
+
+fn disjoint(a: &str, b: &str) -> bool {
+    !a.is_empty() && !b.is_empty() && !a.starts_with(b) && !b.starts_with(a)
+}
+
+/// Variants are tried in order, so a unit variant matching "a" listed before one starting with
+/// "ab" means the second can never match. Move the longer one first when nothing in between could
+/// also match its input, and report an error when that isn't possible or when two unit variants
+/// match the same literal.
+pub fn order_variants(variants: impl IntoIterator<Item = Variant>) -> Result<Vec<Variant>> {
+    let mut variants: Vec<(Variant, Prefix)> = variants
+        .into_iter()
+        .map(|v| prefix(&v).map(|p| (v, p)))
+        .collect::<Result<_>>()?;
+
+    'outer: loop {
+        for i in 0..variants.len() {
+            let Some(exact) = variants[i].1.exact.clone() else {
+                continue;
+            };
+            if exact.is_empty() {
+                continue;
+            }
+
+            for j in (i + 1)..variants.len() {
+                let leading = &variants[j].1.leading;
+                if !leading.starts_with(&exact) {
+                    continue;
+                }
+
+                let (shadowed, shadowing) = (&variants[j].0.ident, &variants[i].0.ident);
+                if variants[j].1.exact.as_ref() == Some(&exact) {
+                    return Err(Error::new(
+                        shadowed.span(),
+                        format!("variant `{shadowed}` is unreachable, `{shadowing}` always matches {exact:?} first"),
+                    ));
+                }
+
+                if !variants[(i + 1)..j]
+                    .iter()
+                    .all(|(_, p)| disjoint(&p.leading, leading))
+                {
+                    return Err(Error::new(
+                        shadowed.span(),
+                        format!("variant `{shadowed}` is shadowed by `{shadowing}`, which matches {exact:?}; move it before `{shadowing}`"),
+                    ));
+                }
+
+                let v = variants.remove(j);
+                variants.insert(i, v);
+                continue 'outer;
+            }
+        }
+        break;
+    }
+
+    Ok(variants.into_iter().map(|(v, _)| v).collect())
+}
@@ -0,0 +1,160 @@
+#![feature(type_alias_impl_trait, impl_trait_in_assoc_type)]
+
+use advent::prelude::*;
+use std::cmp;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(HasParser)]
+struct ChangeDir {
+    #[parse(before = "$ cd ", after = "\n")]
+    path: CommandPath,
+}
+
+#[derive(HasParser)]
+struct ListDir {
+    #[parse(before = "$ ls\n")]
+    entries: List<Entry, TermWith<NewLine>>,
+}
+
+struct CommandPath(PathBuf);
+
+impl HasParser for CommandPath {
+    #[into_parser]
+    fn parser() -> _ {
+        many1(alpha_num().or(char('/')).or(char('.'))).map(|s: String| Self(PathBuf::from(s)))
+    }
+}
+
+impl HasGrammar for CommandPath {
+    fn grammar() -> Grammar {
+        Grammar::Terminal("path")
+    }
+}
+
+#[derive(HasParser)]
+enum Entry {
+    #[parse(before = "dir ")]
+    Dir(CommandPath),
+    File(u64, CommandPath),
+}
+
+#[derive(HasParser)]
+enum Command {
+    Cd(ChangeDir),
+    Ls(ListDir),
+}
+
+#[derive(Default, Debug)]
+struct Node {
+    space_used: u64,
+}
+
+pub struct Fs(BTreeMap<PathBuf, Node>);
+
+impl Fs {
+    fn build(commands: List<Command, Nil>) -> Self {
+        let mut fs = Self(BTreeMap::new());
+        let mut cwd = PathBuf::from("/");
+
+        for cmd in commands {
+            match cmd {
+                Command::Cd(c) => {
+                    if c.path.0 == Path::new("..") {
+                        cwd.pop();
+                    } else {
+                        cwd.push(c.path.0);
+                    }
+                }
+                Command::Ls(l) => {
+                    for e in l.entries {
+                        if let Entry::File(size, _) = e {
+                            fs.add_size_to_dir(cwd.clone(), size);
+                        }
+                    }
+                }
+            }
+        }
+
+        fs
+    }
+
+    fn add_size_to_dir(&mut self, mut path: PathBuf, size: u64) {
+        loop {
+            let node = self.0.entry(path.to_owned()).or_insert(Node::default());
+            node.space_used += size;
+
+            if &path == Path::new("/") {
+                break;
+            }
+
+            path.pop();
+        }
+    }
+
+    fn dirs(&self) -> impl Iterator<Item = &Node> {
+        self.0.values()
+    }
+
+    fn get_root(&self) -> &Node {
+        self.0.get(Path::new("/")).unwrap()
+    }
+}
+
+#[derive(Params)]
+pub struct Disk {
+    size: u64,
+    needed: u64,
+    small_dir: u64,
+}
+
+impl Default for Disk {
+    fn default() -> Self {
+        Self {
+            size: 70_000_000,
+            needed: 30_000_000,
+            small_dir: 100000,
+        }
+    }
+}
+
+#[parse_input]
+fn build_fs(commands: List<Command, Nil>) -> Fs {
+    Fs::build(commands)
+}
+
+#[part_one]
+fn part_one(fs: &Fs, disk: &Disk) -> u64 {
+    let mut total_size = 0;
+    for n in fs.dirs() {
+        if n.space_used <= disk.small_dir {
+            total_size += n.space_used;
+        }
+    }
+
+    total_size
+}
+
+#[part_two]
+fn part_two(fs: &Fs, disk: &Disk) -> u64 {
+    let used_space = fs.get_root().space_used;
+    let free_space = disk.size - used_space;
+    assert!(free_space < disk.needed, "{free_space}");
+
+    let mut smallest_dir_to_delete = u64::MAX;
+    for n in fs.dirs() {
+        if n.space_used + free_space >= disk.needed {
+            smallest_dir_to_delete = cmp::min(n.space_used, smallest_dir_to_delete);
+        }
+    }
+
+    smallest_dir_to_delete
+}
+
+harness!(
+    part_1: 1491614,
+    part_2: 6400111,
+    examples: {
+        "example.txt" => (part_1: 95437, part_2: 24933642),
+    },
+);
@@ -0,0 +1,114 @@
+#![feature(type_alias_impl_trait, impl_trait_in_assoc_type)]
+
+use advent::prelude::*;
+
+#[derive(HasParser)]
+pub enum Instruction {
+    #[parse(string = "noop")]
+    Noop,
+    #[parse(before = "addx ")]
+    Addx(i32),
+}
+
+struct Machine {
+    signal_strengths: Vec<i32>,
+    x_reg: i32,
+    cycle: i32,
+    pc: usize,
+    mid_instruction: bool,
+}
+
+impl Machine {
+    fn new() -> Self {
+        Self {
+            signal_strengths: vec![0; 300],
+            x_reg: 1,
+            cycle: 1,
+            pc: 0,
+            mid_instruction: false,
+        }
+    }
+
+    fn execute(&mut self, instr: &[Instruction]) {
+        if let Instruction::Addx(v) = instr[self.pc] {
+            if self.mid_instruction {
+                self.cycle += 1;
+                self.x_reg += v;
+                self.signal_strengths[self.cycle as usize] = self.cycle * self.x_reg;
+                self.mid_instruction = false;
+                self.pc += 1;
+            } else {
+                self.cycle += 1;
+                self.signal_strengths[self.cycle as usize] = self.cycle * self.x_reg;
+                self.mid_instruction = true;
+            }
+        } else {
+            self.cycle += 1;
+            self.signal_strengths[self.cycle as usize] = self.cycle * self.x_reg;
+            self.pc += 1;
+        }
+    }
+}
+
+struct Crt {
+    pixel: usize,
+    screen: Grid<bool>,
+}
+
+impl Crt {
+    fn new() -> Self {
+        Self {
+            pixel: 0,
+            screen: Grid::new(40, 6, false),
+        }
+    }
+
+    fn draw(&mut self, x_reg: i32) {
+        let (x, y) = (self.pixel % 40, self.pixel / 40);
+        self.screen[(x, y)] = (x as i32 - x_reg).abs() <= 1;
+        self.pixel += 1;
+    }
+}
+
+#[part_one]
+fn part_one(input: &List<Instruction, TermWith<NewLine>>) -> i32 {
+    let mut m = Machine::new();
+    while m.pc < input.len() {
+        m.execute(&input[..]);
+    }
+
+    [20, 60, 100, 140, 180, 220]
+        .into_iter()
+        .map(|i| m.signal_strengths[i])
+        .sum()
+}
+
+#[part_two(ocr)]
+fn part_two(input: &List<Instruction, TermWith<NewLine>>) -> Grid<bool> {
+    let mut m = Machine::new();
+    let mut crt = Crt::new();
+    while m.pc < input.len() {
+        crt.draw(m.x_reg);
+        m.execute(&input[..]);
+    }
+
+    crt.screen
+}
+
+#[cfg(test)]
+const EXAMPLE_PART_2: &str = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+";
+
+harness!(
+    part_1: 16020,
+    part_2: "ECZUZALR",
+    examples: {
+        "example.txt" => (part_1: 13140, part_2: EXAMPLE_PART_2),
+    },
+);
@@ -0,0 +1,123 @@
+#![feature(type_alias_impl_trait, impl_trait_in_assoc_type)]
+
+use advent::prelude::*;
+
+#[derive(Copy, Clone)]
+#[repr(u32)]
+enum Play {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+#[derive(Copy, Clone)]
+#[repr(u32)]
+enum Outcome {
+    Win = 6,
+    Draw = 3,
+    Lose = 0,
+}
+
+impl Outcome {
+    fn to_win(&self, opponent: Play) -> Play {
+        match (*self, opponent) {
+            (Self::Win, Play::Rock) => Play::Paper,
+            (Self::Win, Play::Paper) => Play::Scissors,
+            (Self::Win, Play::Scissors) => Play::Rock,
+            (Self::Lose, Play::Rock) => Play::Scissors,
+            (Self::Lose, Play::Paper) => Play::Rock,
+            (Self::Lose, Play::Scissors) => Play::Paper,
+            (Self::Draw, p) => p,
+        }
+    }
+}
+
+impl HasParser for Play {
+    #[into_parser]
+    fn parser() -> _ {
+        choice((
+            char('A').map(|_| Self::Rock),
+            char('B').map(|_| Self::Paper),
+            char('C').map(|_| Self::Scissors),
+        ))
+    }
+}
+
+impl HasGrammar for Play {
+    fn grammar() -> Grammar {
+        Grammar::Choice(["A", "B", "C"].into_iter().map(Grammar::literal).collect())
+    }
+}
+
+impl Play {
+    fn vs(&self, other: Self) -> Outcome {
+        match (*self, other) {
+            (Self::Rock, Self::Paper) => Outcome::Lose,
+            (Self::Rock, Self::Scissors) => Outcome::Win,
+            (Self::Paper, Self::Rock) => Outcome::Win,
+            (Self::Paper, Self::Scissors) => Outcome::Lose,
+            (Self::Scissors, Self::Rock) => Outcome::Lose,
+            (Self::Scissors, Self::Paper) => Outcome::Win,
+            _ => Outcome::Draw,
+        }
+    }
+}
+
+/// The second column, read as a play in part one and an outcome in part two.
+#[derive(Copy, Clone, HasParser)]
+enum Column {
+    #[parse(string = "X")]
+    X,
+    #[parse(string = "Y")]
+    Y,
+    #[parse(string = "Z")]
+    Z,
+}
+
+impl Column {
+    fn play(self) -> Play {
+        match self {
+            Self::X => Play::Rock,
+            Self::Y => Play::Paper,
+            Self::Z => Play::Scissors,
+        }
+    }
+
+    fn outcome(self) -> Outcome {
+        match self {
+            Self::X => Outcome::Lose,
+            Self::Y => Outcome::Draw,
+            Self::Z => Outcome::Win,
+        }
+    }
+}
+
+#[derive(HasParser)]
+pub struct Entry {
+    opponent: Play,
+    column: Column,
+}
+
+impl Entry {
+    fn score_as_play(&self) -> u32 {
+        let mine = self.column.play();
+        mine as u32 + mine.vs(self.opponent) as u32
+    }
+
+    fn score_as_outcome(&self) -> u32 {
+        let outcome = self.column.outcome();
+        outcome as u32 + outcome.to_win(self.opponent) as u32
+    }
+}
+
+#[part_one]
+fn part_one(i: &List<Entry, TermWith<NewLine>>) -> u32 {
+    i.iter().map(|e| e.score_as_play()).sum()
+}
+
+#[part_two]
+fn part_two(i: &List<Entry, TermWith<NewLine>>) -> u32 {
+    i.iter().map(|e| e.score_as_outcome()).sum()
+}
+
+harness!(part_1: 10310, part_2: 14859);